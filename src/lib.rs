@@ -6,12 +6,20 @@ use frame_support::{
 	debug::native,
 	pallet_prelude::*,
 	traits::{
-		Currency as SetheumCurrency, ExistenceRequirement, Get, 
+		ChangeMembers, Currency as SetheumCurrency, ExistenceRequirement, Get, InitializeMembers,
 		LockableCurrency as SetheumLockableCurrency,
 		ReservableCurrency as SetheumReservableCurrency, WithdrawReasons,
 	},
 };
-use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
+use frame_system::{
+	ensure_none, ensure_root, ensure_signed,
+	offchain::{
+		AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
+		SigningTypes,
+	},
+	pallet_prelude::*,
+};
+use lite_json::json::{JsonValue, NumberValue};
 use serp_traits::{
 	account::MergeAccount,
 	arithmetic::{Signed, SimpleArithmetic},
@@ -19,152 +27,3611 @@ use serp_traits::{
 	LockIdentifier, Stp258Currency, Stp258CurrencyExtended, Stp258CurrencyReservable, Stp258CurrencyLockable,
 };
 use orml_utilities::with_transaction_result;
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_core::{crypto::KeyTypeId, U256};
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	traits::{AccountIdConversion, CheckedAdd, CheckedDiv, CheckedSub, DispatchInfoOf, Hash, IdentifyAccount, MaybeSerializeDeserialize, One, PostDispatchInfoOf, Saturating, StaticLookup, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
+	},
+	helpers_128bit::multiply_by_rational,
+	offchain::{http, Duration},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, ModuleId, PerThing, Perbill, Permill, SaturatedConversion,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
 };
 
-mod default_weight;
-mod mock;
-mod tests;
+mod default_weight;
+mod mock;
+mod tests;
+
+pub use module::*;
+
+/// The price of one whole unit (`base_unit`) of a currency, expressed in a
+/// reference currency shared by every currency of the price source.
+pub type Price = FixedU128;
+
+/// The price of one whole unit of `base` in whole units of `quote`, so that
+/// prices of currencies with different base units compare like for like.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct QuotedPrice<CurrencyId> {
+	pub base: CurrencyId,
+	pub quote: CurrencyId,
+	pub price: FixedU128,
+}
+
+/// The direction and size of the change a SERP round makes to a currency's
+/// supply. A round at peg expands it by nothing.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SupplyAdjustment<Balance> {
+	Expand(Balance),
+	Contract(Balance),
+}
+
+/// A mint/burn allowance granted by governance to an account for one currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct MintAuthority<Balance, BlockNumber> {
+	/// The most that may be minted in any `MintPeriod` consecutive blocks,
+	/// unbounded if `None`.
+	pub cap: Option<Balance>,
+	/// The amounts minted per block over the last `MintPeriod` blocks,
+	/// oldest first.
+	pub mints: Vec<(BlockNumber, Balance)>,
+}
+
+/// A recipient of newly expanded supply.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Beneficiary<AccountId> {
+	/// The `TreasuryAccount`.
+	Treasury,
+	/// The `SavingsPoolAccount`.
+	SavingsPool,
+	/// The `SerperPoolAccount`.
+	SerperPool,
+	/// Any other account.
+	Account(AccountId),
+}
+
+/// How the contraction of a currency's supply is executed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ContractionMethod {
+	/// Leave contraction to `Stp258Currency`.
+	Stp258,
+	/// Sell discounted SERP bonds for the contracted amount.
+	Bonds,
+	/// Sell native currency for the contracted amount in a descending-price
+	/// auction.
+	Auction,
+	/// Burn the contracted amount pro rata from the serper pool.
+	SerperPool,
+}
+
+impl Default for ContractionMethod {
+	fn default() -> Self {
+		ContractionMethod::Stp258
+	}
+}
+
+/// The price of a bond, as a fraction of its face value, across a bond
+/// window. Bonds sell at `start_price` when a window opens and rise linearly
+/// to `end_price` as its capacity is sold.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct BondPriceCurve {
+	pub start_price: Perbill,
+	pub end_price: Perbill,
+}
+
+/// The bond capacity opened by contraction rounds and not yet sold.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct BondWindow<Balance> {
+	/// The stable currency to be burned by selling bonds.
+	pub capacity: Balance,
+	/// The stable currency burned so far.
+	pub sold: Balance,
+}
+
+/// A SERP bond, redeemed for its face value on expansion until `expires_at`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Bond<AccountId, Balance, BlockNumber> {
+	pub owner: AccountId,
+	pub amount: Balance,
+	pub expires_at: BlockNumber,
+}
+
+/// Parameters of a currency's contraction auctions.
+///
+/// An auction asks `quote_price * (1 + start_premium)` stable currency per
+/// native currency unit, decaying by `quote_price * decay_per_block` each
+/// block down to `quote_price * (1 - max_discount)`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct AuctionParams<BlockNumber> {
+	pub start_premium: Perbill,
+	pub decay_per_block: Perbill,
+	pub max_discount: Perbill,
+	/// How many blocks an auction stays open.
+	pub duration: BlockNumber,
+}
+
+/// An open contraction auction.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct ContractionAuction<Balance, BlockNumber> {
+	/// The stable currency the auction set out to buy.
+	pub target: Balance,
+	/// The stable currency still to be bought.
+	pub remaining: Balance,
+	/// The quote price the auction price is anchored on.
+	pub quote_price: Balance,
+	pub start: BlockNumber,
+	pub end: BlockNumber,
+}
+
+/// The totals of a currency's serper pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct SerperPoolInfo<Balance> {
+	/// The stable currency reserved by all members.
+	pub total: Balance,
+	/// The number of members.
+	pub members: u32,
+}
+
+/// The reserves of a native/stable liquidity pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct LiquidityPool<Balance> {
+	/// The native currency in the pool.
+	pub native: Balance,
+	/// The stable currency in the pool.
+	pub stable: Balance,
+	/// The liquidity shares issued to providers.
+	pub shares: Balance,
+}
+
+/// The cumulative spot price of a liquidity pool at a block.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct PriceObservation<BlockNumber> {
+	/// The sum of the pool's spot price over every block so far.
+	pub price_cumulative: Price,
+	pub at: BlockNumber,
+}
+
+/// The terms of a peg stability module pair.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct PsmParams<Balance> {
+	/// The fee taken from the stable currency minted for collateral.
+	pub mint_fee: Permill,
+	/// The fee taken from the collateral paid out for stable currency.
+	pub redeem_fee: Permill,
+	/// The most stable currency the pair's collateral may back.
+	pub debt_ceiling: Balance,
+}
+
+/// Oracle prices signed by an operator's key, submitted without a fee.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PricePayload<Public, BlockNumber, CurrencyId> {
+	pub public: Public,
+	/// The block the prices were fetched at.
+	pub block_number: BlockNumber,
+	/// The operator's oracle nonce, see `OracleNonces`.
+	pub nonce: u64,
+	pub prices: Vec<(CurrencyId, Price)>,
+}
+
+impl<T: SigningTypes, CurrencyId: Encode> SignedPayload<T> for PricePayload<T::Public, T::BlockNumber, CurrencyId> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// The schedule of commit–reveal oracle rounds. Each cycle of
+/// `commit_period + reveal_period` blocks from `start` opens with its commit
+/// window.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct CommitRevealSchedule<BlockNumber> {
+	pub commit_period: BlockNumber,
+	pub reveal_period: BlockNumber,
+	pub start: BlockNumber,
+}
+
+/// An operator's commitment to `hash(price, salt)` in a commit–reveal cycle.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct PriceCommitment<Hash> {
+	pub cycle: u32,
+	pub hash: Hash,
+}
+
+/// Where in the fallback chain a price came from.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PriceSourceKind {
+	/// The provider at this position of `Config::PriceSource`.
+	Provider(u32),
+	/// The last good price, within `Config::MaxPriceStaleness`.
+	LastGood,
+}
+
+/// A source of prices for the currencies handled by the SERP.
+pub trait PriceProvider<CurrencyId> {
+	/// The current price of `currency_id`, if known.
+	fn get_price(currency_id: CurrencyId) -> Option<Price>;
+}
+
+/// An ordered list of price providers, implemented for tuples of
+/// `PriceProvider`s.
+pub trait PriceProviders<CurrencyId> {
+	/// The price of `currency_id` from the first provider that has one, and
+	/// the position of that provider in the list.
+	fn get_price(currency_id: CurrencyId) -> Option<(Price, u32)>;
+}
+
+macro_rules! impl_price_providers {
+	($($provider:ident => $position:expr),+) => {
+		impl<CurrencyId: Copy, $($provider: PriceProvider<CurrencyId>),+> PriceProviders<CurrencyId> for ($($provider,)+) {
+			fn get_price(currency_id: CurrencyId) -> Option<(Price, u32)> {
+				$(
+					if let Some(price) = $provider::get_price(currency_id) {
+						return Some((price, $position));
+					}
+				)+
+				None
+			}
+		}
+	};
+}
+
+impl_price_providers!(A => 0);
+impl_price_providers!(A => 0, B => 1);
+impl_price_providers!(A => 0, B => 1, C => 2);
+impl_price_providers!(A => 0, B => 1, C => 2, D => 3);
+
+/// The key type of the keys signing oracle price submissions.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"serp");
+
+/// How long, in milliseconds, the off-chain worker waits for the price
+/// endpoint.
+const FETCH_TIMEOUT_PERIOD: u64 = 3_000;
+
+/// The application crypto of the oracle operators' keys.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Signs oracle price submissions with an operator's `serp` key.
+	pub struct OracleAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OracleAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub trait WeightInfo {
+		fn transfer_non_native_currency() -> Weight;
+		fn transfer_native_currency() -> Weight;
+		fn update_balance_non_native_currency() -> Weight;
+		fn update_balance_native_currency_creating() -> Weight;
+		fn update_balance_native_currency_killing() -> Weight;
+		fn transfer_with_memo_non_native_currency() -> Weight;
+		fn transfer_with_memo_native_currency() -> Weight;
+		fn set_fee_currency() -> Weight;
+		fn grant_mint_authority() -> Weight;
+		fn revoke_mint_authority() -> Weight;
+		fn mint() -> Weight;
+		fn burn() -> Weight;
+		fn set_issuance_bounds() -> Weight;
+		fn set_expansion_distribution(b: u32) -> Weight;
+		fn set_contraction_method() -> Weight;
+		fn set_bond_curve() -> Weight;
+		fn buy_bonds() -> Weight;
+		fn set_auction_params() -> Weight;
+		fn bid() -> Weight;
+		fn join_serper_pool() -> Weight;
+		fn leave_serper_pool() -> Weight;
+		fn withdraw_unbonded() -> Weight;
+		fn claim_rewards() -> Weight;
+		fn fund_reserve() -> Weight;
+		fn set_min_collateral_ratio() -> Weight;
+		fn exclude_from_circulation() -> Weight;
+		fn include_in_circulation() -> Weight;
+		fn set_oracle_symbol(s: u32) -> Weight;
+		fn submit_prices(p: u32) -> Weight;
+		fn submit_prices_unsigned(p: u32) -> Weight;
+		fn add_operator() -> Weight;
+		fn remove_operator(s: u32) -> Weight;
+		fn swap_operator(s: u32) -> Weight;
+		fn set_max_price_jump() -> Weight;
+		fn unpause_serp() -> Weight;
+		fn set_commit_reveal(c: u32) -> Weight;
+		fn bond_oracle() -> Weight;
+		fn unbond_oracle(c: u32) -> Weight;
+		fn commit_price() -> Weight;
+		fn reveal_price() -> Weight;
+		fn add_liquidity() -> Weight;
+		fn remove_liquidity() -> Weight;
+		fn pool_swap() -> Weight;
+		fn set_psm_params() -> Weight;
+		fn psm_mint() -> Weight;
+		fn psm_redeem() -> Weight;
+		fn set_peg_window_limit() -> Weight;
+		fn mint_at_peg() -> Weight;
+		fn redeem_at_peg() -> Weight;
+	}
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type AmountOf<T> =
+		<<T as Config>::Stp258Currency as Stp258CurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
+	pub(crate) type BlockOf<T> =
+		<<T as Config>::Stp258Currency as SerpTes<<T as frame_system::Config>::AccountId>>::BlockNumber;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		type Stp258Currency: MergeAccount<Self::AccountId>
+			+ Stp258CurrencyExtended<Self::AccountId>
+			+ Stp258CurrencyLockable<Self::AccountId>
+			+ Stp258CurrencyReservable<Self::AccountId>
+			+ SerpMarket<Self::AccountId>
+			+ SerpTes<Self::AccountId>;
+
+		type Stp258Native: Stp258AssetExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
+			+ Stp258AssetLockable<Self::AccountId, Balance = BalanceOf<Self>>
+			+ Stp258AssetReservable<Self::AccountId, Balance = BalanceOf<Self>>;
+
+		#[pallet::constant]
+		type GetStp258NativeId: Get<CurrencyIdOf<Self>>;
+
+		/// The maximum length of a memo attached to a transfer.
+		#[pallet::constant]
+		type MaxMemoLength: Get<u32>;
+
+		/// The sources of currency prices, used to check SERP quotes, value
+		/// reserves and convert native fees into stable currencies. A tuple of
+		/// providers, each used only when those before it have no price.
+		type PriceSource: PriceProviders<CurrencyIdOf<Self>>;
+
+		/// The number of blocks the last good price of a currency may be used
+		/// for once no source has a price.
+		#[pallet::constant]
+		type MaxPriceStaleness: Get<Self::BlockNumber>;
+
+		/// The markup added on top of the converted fee when transaction fees
+		/// are paid in a stable currency.
+		#[pallet::constant]
+		type FeeMarkup: Get<Permill>;
+
+		/// The origin which may update the SERP-TES parameters.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The length of the rolling period over which a mint authority's cap
+		/// applies.
+		#[pallet::constant]
+		type MintPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of blocks a capped mint authority may mint in
+		/// per `MintPeriod`.
+		#[pallet::constant]
+		type MaxMintsPerPeriod: Get<u32>;
+
+		/// The treasury account receiving its share of expanded supply.
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The savings pool account receiving its share of expanded supply.
+		type SavingsPoolAccount: Get<Self::AccountId>;
+
+		/// The serper pool account receiving its share of expanded supply.
+		type SerperPoolAccount: Get<Self::AccountId>;
+
+		/// The maximum number of beneficiaries in an expansion distribution.
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
+		/// How long a bond can be redeemed for after it is bought.
+		#[pallet::constant]
+		type BondDuration: Get<Self::BlockNumber>;
+
+		/// The maximum number of bonds processed by one expansion.
+		#[pallet::constant]
+		type MaxBondRedemptions: Get<u32>;
+
+		/// The maximum number of members of a currency's serper pool.
+		#[pallet::constant]
+		type MaxSerperPoolMembers: Get<u32>;
+
+		/// How long stable currency leaving the serper pool stays reserved.
+		#[pallet::constant]
+		type SerperCooldown: Get<Self::BlockNumber>;
+
+		/// The key the off-chain worker signs price submissions with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The HTTP endpoint the off-chain worker fetches prices from, serving
+		/// a JSON object of prices keyed by currency symbol.
+		type PriceEndpoint: Get<&'static str>;
+
+		/// The number of blocks between two price fetches by the off-chain
+		/// worker.
+		#[pallet::constant]
+		type OracleInterval: Get<Self::BlockNumber>;
+
+		/// Whether the off-chain worker submits prices unsigned, in a payload
+		/// signed by the operator's oracle key, rather than in transactions
+		/// signed and paid for by the operator.
+		#[pallet::constant]
+		type UnsignedSubmissions: Get<bool>;
+
+		/// The priority of unsigned price submissions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// The number of blocks an unsigned price submission stays valid
+		/// for after the block its prices were fetched at.
+		#[pallet::constant]
+		type UnsignedLongevity: Get<Self::BlockNumber>;
+
+		/// The origin which may add, remove and swap oracle operators.
+		type OperatorOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of oracle operators.
+		#[pallet::constant]
+		type MaxOperators: Get<u32>;
+
+		/// The number of operator submissions a round needs before it is
+		/// aggregated.
+		#[pallet::constant]
+		type OracleQuorum: Get<u32>;
+
+		/// The most a submission may deviate from a round's median before it
+		/// is rejected as an outlier.
+		#[pallet::constant]
+		type MaxOracleDeviation: Get<Permill>;
+
+		/// The number of blocks an oracle round stays open after its first
+		/// submission. The submissions of an older round are discarded.
+		#[pallet::constant]
+		type RoundTimeout: Get<Self::BlockNumber>;
+
+		/// The native currency an operator reserves to take part in
+		/// commit–reveal rounds.
+		#[pallet::constant]
+		type OracleBond: Get<BalanceOf<Self>>;
+
+		/// The native currency slashed from an operator's bond for each
+		/// commitment left unrevealed or revealed wrongly.
+		#[pallet::constant]
+		type OracleSlash: Get<BalanceOf<Self>>;
+
+		/// The maximum number of price commitments made in one commit–reveal
+		/// cycle.
+		#[pallet::constant]
+		type MaxCommitmentsPerCycle: Get<u32>;
+
+		/// The number of consecutive price jump rejections after which a
+		/// currency's SERP is paused.
+		#[pallet::constant]
+		type MaxPriceJumpRejections: Get<u32>;
+
+		/// The maximum length of a currency's oracle symbol.
+		#[pallet::constant]
+		type MaxSymbolLength: Get<u32>;
+
+		/// The maximum number of currencies with an oracle symbol.
+		#[pallet::constant]
+		type MaxOracleSymbols: Get<u32>;
+
+		/// The maximum number of accounts excluded from a currency's
+		/// circulating supply.
+		#[pallet::constant]
+		type MaxExcludedAccounts: Get<u32>;
+
+		/// The most a SERP quote price may deviate from the price source's
+		/// reference price.
+		#[pallet::constant]
+		type MaxQuoteDeviation: Get<Permill>;
+
+		/// The maximum number of attempts made to partially fill a
+		/// contraction.
+		#[pallet::constant]
+		type MaxContractionAttempts: Get<u32>;
+
+		/// The SERP-TES module id, used to derive the reserve account of each
+		/// stable currency.
+		#[pallet::constant]
+		type ModuleId: Get<ModuleId>;
+
+		/// The fee taken from the supplied amount of each liquidity pool swap.
+		#[pallet::constant]
+		type PoolSwapFee: Get<Permill>;
+
+		/// The number of blocks a liquidity pool's time-weighted average price
+		/// is taken over.
+		#[pallet::constant]
+		type TwapPeriod: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Unable to convert the Amount type into Balance.
+		AmountIntoBalanceFailed,
+		/// Balance is too low.
+		BalanceTooLow,
+		/// The transfer memo is longer than `MaxMemoLength`.
+		MemoTooLong,
+		/// The currency cannot be used to pay transaction fees.
+		InvalidFeeCurrency,
+		/// The account has no mint authority for the currency.
+		NoMintAuthority,
+		/// Minting would exceed the mint authority's cap for this period.
+		MintCapExceeded,
+		/// The mint authority minted in `MaxMintsPerPeriod` blocks of this
+		/// period already.
+		TooManyMints,
+		/// The operation would push total issuance above the currency's maximum.
+		SupplyCeilingReached,
+		/// The operation would push total issuance below the currency's minimum.
+		SupplyFloorReached,
+		/// The minimum issuance is above the maximum issuance.
+		InvalidIssuanceBounds,
+		/// The distribution shares do not sum to 100%.
+		InvalidDistribution,
+		/// The distribution has more than `MaxBeneficiaries` beneficiaries.
+		TooManyBeneficiaries,
+		/// The bond price curve is zero or falling.
+		InvalidBondCurve,
+		/// No bond price curve is set for the currency.
+		BondCurveNotSet,
+		/// No contraction is waiting to be filled by bonds.
+		NoBondCapacity,
+		/// Bonds are only sold while the last accepted SERP price is below
+		/// the peg.
+		BondsNotOnSale,
+		/// The auction parameters are invalid.
+		InvalidAuctionParams,
+		/// No auction parameters are set for the currency.
+		AuctionParamsNotSet,
+		/// No contraction auction is open for the currency.
+		AuctionNotOpen,
+		/// The bid is too small to buy any native currency.
+		BidTooSmall,
+		/// The serper pool has reached `MaxSerperPoolMembers`.
+		SerperPoolFull,
+		/// The account's serper pool stake is too low.
+		InsufficientSerperStake,
+		/// The serper pool cannot cover the contraction.
+		InsufficientSerperPool,
+		/// The account has nothing leaving the serper pool.
+		NothingToWithdraw,
+		/// The serper pool cooldown has not yet passed.
+		SerperCooldownActive,
+		/// The account has no rewards to claim.
+		NoRewards,
+		/// The quote price is zero or cannot be represented.
+		InvalidQuotePrice,
+		/// The quote price deviates too far from the price source.
+		QuoteDeviationTooLarge,
+		/// The account is already excluded from the circulating supply.
+		AlreadyExcluded,
+		/// The account is not excluded from the circulating supply.
+		NotExcluded,
+		/// More than `MaxExcludedAccounts` accounts would be excluded.
+		TooManyExcludedAccounts,
+		/// The oracle symbol is longer than `MaxSymbolLength`.
+		SymbolTooLong,
+		/// `MaxOracleSymbols` currencies already have an oracle symbol.
+		TooManyOracleSymbols,
+		/// The account is not an oracle operator.
+		NotOperator,
+		/// The account is already an oracle operator.
+		AlreadyOperator,
+		/// More than `MaxOperators` operators would be set.
+		TooManyOperators,
+		/// The currency has no oracle symbol.
+		UnknownOracleCurrency,
+		/// Oracle prices must not be zero.
+		InvalidOraclePrice,
+		/// The currency's SERP is not paused.
+		SerpNotPaused,
+		/// The currency's SERP is paused.
+		SerpPaused,
+		/// Prices must be committed and revealed while commit–reveal rounds
+		/// are enabled.
+		CommitRevealActive,
+		/// Commit–reveal rounds are not enabled.
+		CommitRevealInactive,
+		/// Commit and reveal periods must not be zero.
+		InvalidCommitRevealPeriods,
+		/// The commit window of the current cycle is closed.
+		NotCommitPhase,
+		/// The reveal window of the current cycle is not open.
+		NotRevealPhase,
+		/// The operator already committed a price in this cycle.
+		AlreadyCommitted,
+		/// The operator has no commitment to reveal in this cycle.
+		NoCommitment,
+		/// The operator's bond does not cover a slash.
+		OracleNotBonded,
+		/// The account already has an oracle bond.
+		AlreadyBonded,
+		/// The operator still has commitments to reveal.
+		CommitmentsOutstanding,
+		/// `MaxCommitmentsPerCycle` commitments were already made this cycle.
+		TooManyCommitments,
+		/// Liquidity pools pair the native currency with a stable currency.
+		InvalidPoolCurrency,
+		/// Pool amounts must not be zero.
+		InvalidPoolAmount,
+		/// The pool does not have the liquidity for this.
+		InsufficientPoolLiquidity,
+		/// The account does not have that many liquidity shares.
+		InsufficientLiquidityShares,
+		/// The pool's rate moved beyond the given limit.
+		PoolSlippageExceeded,
+		/// A peg stability module pairs a stable currency with another
+		/// non-native currency.
+		InvalidPsmPair,
+		/// The peg stability module pair is not configured.
+		PsmPairNotFound,
+		/// The swap would take the pair over its debt ceiling.
+		PsmDebtCeilingExceeded,
+		/// The pair holds less collateral than the stable currency redeemed.
+		InsufficientPsmCollateral,
+		/// The amount cannot be represented once converted at the peg.
+		PegConversionOverflow,
+		/// Mint/redeem at peg windows are for stable currencies.
+		InvalidPegWindowCurrency,
+		/// The currency has no mint/redeem at peg window.
+		PegWindowClosed,
+		/// The swap would take the window over its limit for this round.
+		PegWindowLimitExceeded,
+		/// The stable currency is not above its peg.
+		NotAbovePeg,
+		/// The stable currency is not below its peg.
+		NotBelowPeg,
+		/// The reserve ratio of the currency is below its minimum.
+		ReserveRatioUnhealthy,
+		/// A price needed to compute the collateral ratio is unavailable.
+		PriceUnavailable,
+		/// The operation would push the collateral ratio below its minimum.
+		CollateralRatioTooLow,
+		/// The reserve cannot cover the native currency payout.
+		InsufficientReserve,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Currency transfer success. [currency_id, from, to, amount]
+		Transferred(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Currency transfer with memo success. [currency_id, from, to, amount, memo]
+		TransferredWithMemo(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>, Vec<u8>),
+		/// Update balance success. [currency_id, who, amount]
+		BalanceUpdated(CurrencyIdOf<T>, T::AccountId, AmountOf<T>),
+		/// Deposit success. [currency_id, who, amount]
+		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Withdraw success. [currency_id, who, amount]
+		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		// Supply Expansion Successful. \[currency_id, expand_by\]
+		SerpedUpSupply(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Supply Contraction Successful. \[currency_id, contract_by\]
+		SerpedDownSupply(CurrencyIdOf<T>, BalanceOf<T>),
+		// Serp Elast Successful. \[currency_id, expand_by\]
+		SerpElast(CurrencyIdOf<T>),
+		/// On Serp Block Successful. \[currency_id, contract_by\]
+		OnSerpBlock(CurrencyIdOf<T>),
+		/// Fee currency selected, `None` meaning native. \[who, currency_id\]
+		FeeCurrencySet(T::AccountId, Option<CurrencyIdOf<T>>),
+		/// Mint authority granted. \[currency_id, who, cap\]
+		MintAuthorityGranted(CurrencyIdOf<T>, T::AccountId, Option<BalanceOf<T>>),
+		/// Mint authority revoked. \[currency_id, who\]
+		MintAuthorityRevoked(CurrencyIdOf<T>, T::AccountId),
+		/// Currency minted by a mint authority. \[currency_id, minter, to, amount\]
+		Minted(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Currency burned by a mint authority. \[currency_id, burner, amount\]
+		Burned(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Issuance bounds updated. \[currency_id, min_issuance, max_issuance\]
+		IssuanceBoundsSet(CurrencyIdOf<T>, Option<BalanceOf<T>>, Option<BalanceOf<T>>),
+		/// Expansion distribution updated. \[currency_id\]
+		ExpansionDistributionSet(CurrencyIdOf<T>),
+		/// Expanded supply distributed. \[currency_id, payouts\]
+		ExpansionDistributed(CurrencyIdOf<T>, Vec<(T::AccountId, BalanceOf<T>)>),
+		/// Contraction method updated. \[currency_id, method\]
+		ContractionMethodSet(CurrencyIdOf<T>, ContractionMethod),
+		/// Bond price curve updated. \[currency_id, curve\]
+		BondCurveSet(CurrencyIdOf<T>, BondPriceCurve),
+		/// Contraction opened for bond sales. \[currency_id, amount\]
+		BondCapacityOpened(CurrencyIdOf<T>, BalanceOf<T>),
+		/// Bonds bought. \[currency_id, who, paid, face_value\]
+		BondsPurchased(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Bond redeemed on expansion. \[currency_id, who, amount\]
+		BondRedeemed(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Bond expired unredeemed. \[currency_id, who, amount\]
+		BondExpired(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Auction parameters updated. \[currency_id\]
+		AuctionParamsSet(CurrencyIdOf<T>),
+		/// Contraction auction opened or topped up. \[currency_id, remaining, end\]
+		AuctionOpened(CurrencyIdOf<T>, BalanceOf<T>, T::BlockNumber),
+		/// Contraction auction bid filled. \[currency_id, who, stable_amount, native_amount\]
+		AuctionBid(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Contraction auction closed. \[currency_id, target, unfilled\]
+		AuctionClosed(CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Stable currency added to the serper pool. \[currency_id, who, amount\]
+		SerperPoolJoined(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Stable currency leaving the serper pool. \[currency_id, who, amount, unlock_at\]
+		SerperPoolLeft(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// Stable currency unreserved after the cooldown. \[currency_id, who, amount\]
+		SerperWithdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// A serper pool member's share of a contraction. \[currency_id, who, burned, native_paid\]
+		SerperContracted(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Serper pool rewards claimed. \[currency_id, who, amount\]
+		RewardsClaimed(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Native currency added to a stable currency's reserve. \[currency_id, who, amount\]
+		ReserveFunded(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Minimum collateral ratio updated. \[currency_id, ratio\]
+		MinCollateralRatioSet(CurrencyIdOf<T>, Option<FixedU128>),
+		/// A contraction was only partially filled. \[currency_id, requested, filled\]
+		ContractionPartiallyFilled(CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Outstanding contraction debt changed. \[currency_id, debt\]
+		ContractionDebtChanged(CurrencyIdOf<T>, BalanceOf<T>),
+		/// A SERP quote price was rejected. \[currency_id, quote_price, reference_price\]
+		QuoteRejected(CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Account excluded from the circulating supply. \[currency_id, who\]
+		ExcludedFromCirculation(CurrencyIdOf<T>, T::AccountId),
+		/// Account included in the circulating supply again. \[currency_id, who\]
+		IncludedInCirculation(CurrencyIdOf<T>, T::AccountId),
+		/// Oracle symbol of a currency updated. \[currency_id, symbol\]
+		OracleSymbolSet(CurrencyIdOf<T>, Option<Vec<u8>>),
+		/// Commit–reveal periods updated. \[periods\]
+		CommitRevealSet(Option<(T::BlockNumber, T::BlockNumber)>),
+		/// Oracle bond reserved. \[who, amount\]
+		OracleBonded(T::AccountId, BalanceOf<T>),
+		/// Oracle bond released. \[who, amount\]
+		OracleUnbonded(T::AccountId, BalanceOf<T>),
+		/// Oracle price committed. \[currency_id, who\]
+		PriceCommitted(CurrencyIdOf<T>, T::AccountId),
+		/// Committed oracle price revealed. \[currency_id, who, price\]
+		PriceRevealed(CurrencyIdOf<T>, T::AccountId, Price),
+		/// Oracle bond slashed for an unrevealed or mismatching commitment. \[currency_id, who, amount\]
+		OracleSlashed(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// The price a SERP round used and its source. \[currency_id, price, source\]
+		PriceSourced(CurrencyIdOf<T>, Price, PriceSourceKind),
+		/// Maximum price jump between SERP rounds updated. \[currency_id, max_jump\]
+		MaxPriceJumpSet(CurrencyIdOf<T>, Option<Permill>),
+		/// SERP round skipped as its price jumped too far. \[currency_id, last_price, price\]
+		PriceJumpRejected(CurrencyIdOf<T>, Price, Price),
+		/// SERP paused after too many rejected price jumps. \[currency_id\]
+		SerpPaused(CurrencyIdOf<T>),
+		/// SERP unpaused by governance. \[currency_id\]
+		SerpUnpaused(CurrencyIdOf<T>),
+		/// Oracle operator added. \[who\]
+		OperatorAdded(T::AccountId),
+		/// Oracle operator removed. \[who\]
+		OperatorRemoved(T::AccountId),
+		/// Oracle operator swapped for another account. \[removed, added\]
+		OperatorSwapped(T::AccountId, T::AccountId),
+		/// Members beyond `MaxOperators` left out of the oracle operators. \[dropped\]
+		OperatorsTruncated(Vec<T::AccountId>),
+		/// Oracle price submitted. \[currency_id, operator, price\]
+		PriceSubmitted(CurrencyIdOf<T>, T::AccountId, Price),
+		/// Oracle submission rejected as an outlier. \[currency_id, operator, price\]
+		SubmissionRejected(CurrencyIdOf<T>, T::AccountId, Price),
+		/// Oracle round aggregated to its median. \[currency_id, round, price, count\]
+		AggregatedPrice(CurrencyIdOf<T>, u32, Price, u32),
+		/// Oracle round timed out short of its quorum. \[currency_id, round\]
+		RoundTimedOut(CurrencyIdOf<T>, u32),
+		/// Liquidity added to a pool. \[currency_id, who, native_amount, stable_amount, shares\]
+		LiquidityAdded(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Liquidity removed from a pool. \[currency_id, who, native_amount, stable_amount, shares\]
+		LiquidityRemoved(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Pool swap. \[who, supply_currency_id, supply_amount, target_currency_id, target_amount\]
+		PoolSwapped(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, CurrencyIdOf<T>, BalanceOf<T>),
+		/// Peg stability module pair terms set. \[stable_currency_id, collateral_currency_id, params\]
+		PsmParamsSet(CurrencyIdOf<T>, CurrencyIdOf<T>, PsmParams<BalanceOf<T>>),
+		/// Stable currency minted for collateral at the peg. \[stable_currency_id, collateral_currency_id, who, collateral_amount, stable_amount, fee\]
+		PsmMinted(CurrencyIdOf<T>, CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Stable currency redeemed for collateral at the peg. \[stable_currency_id, collateral_currency_id, who, stable_amount, collateral_amount, fee\]
+		PsmRedeemed(CurrencyIdOf<T>, CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Mint/redeem at peg window limit updated. \[currency_id, limit\]
+		PegWindowLimitSet(CurrencyIdOf<T>, Option<BalanceOf<T>>),
+		/// Stable currency minted for burnt native currency at the peg. \[currency_id, who, native_amount, stable_amount\]
+		MintedAtPeg(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Stable currency redeemed for minted native currency at the peg. \[currency_id, who, stable_amount, native_amount\]
+		RedeemedAtPeg(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+	}
+
+	/// The stable currency an account pays its transaction fees in. Accounts
+	/// without an entry pay in the native currency.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_currency)]
+	pub type FeeCurrency<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, CurrencyIdOf<T>, OptionQuery>;
+
+	/// The accounts allowed to mint and burn each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_authority)]
+	pub type MintAuthorities<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		T::AccountId,
+		MintAuthority<BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The total issuance each currency may not exceed.
+	#[pallet::storage]
+	#[pallet::getter(fn max_issuance)]
+	pub type MaxIssuance<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, OptionQuery>;
+
+	/// The total issuance each currency may not fall below.
+	#[pallet::storage]
+	#[pallet::getter(fn min_issuance)]
+	pub type MinIssuance<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, OptionQuery>;
+
+	/// How expanded supply of each currency is shared among beneficiaries.
+	/// Currencies without a distribution leave it to `Stp258Currency`.
+	#[pallet::storage]
+	#[pallet::getter(fn expansion_distribution)]
+	pub type ExpansionDistribution<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<(Beneficiary<T::AccountId>, Perbill)>, ValueQuery>;
+
+	/// How the contraction of each currency is executed.
+	#[pallet::storage]
+	#[pallet::getter(fn contraction_method)]
+	pub type ContractionMethods<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, ContractionMethod, ValueQuery>;
+
+	/// The bond price curve of each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn bond_curve)]
+	pub type BondCurves<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BondPriceCurve, OptionQuery>;
+
+	/// The open bond window of each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn bond_window)]
+	pub type BondWindows<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BondWindow<BalanceOf<T>>, ValueQuery>;
+
+	/// Outstanding bonds of each currency, redeemed first in first out.
+	#[pallet::storage]
+	pub type BondQueue<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		u64,
+		Bond<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The head and tail indices of each currency's `BondQueue`.
+	#[pallet::storage]
+	#[pallet::getter(fn bond_queue_bounds)]
+	pub type BondQueueBounds<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (u64, u64), ValueQuery>;
+
+	/// The total face value of outstanding bonds held by each account.
+	#[pallet::storage]
+	#[pallet::getter(fn bonds_of)]
+	pub type AccountBonds<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The contraction auction parameters of each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_params)]
+	pub type AuctionParameters<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, AuctionParams<T::BlockNumber>, OptionQuery>;
+
+	/// The open contraction auction of each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn auction)]
+	pub type Auctions<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, ContractionAuction<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// The currencies whose contraction auction closes at each block.
+	#[pallet::storage]
+	pub type AuctionExpiries<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Vec<CurrencyIdOf<T>>, ValueQuery>;
+
+	/// Contraction left unfilled by previous rounds, carried into the next.
+	#[pallet::storage]
+	#[pallet::getter(fn contraction_debt)]
+	pub type ContractionDebt<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The totals of each currency's serper pool.
+	#[pallet::storage]
+	#[pallet::getter(fn serper_pool)]
+	pub type SerperPools<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, SerperPoolInfo<BalanceOf<T>>, ValueQuery>;
+
+	/// The reserved stable currency each member has in the serper pool.
+	#[pallet::storage]
+	#[pallet::getter(fn serper_stake)]
+	pub type SerperStakes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Stable currency that left the serper pool, and the block from which it
+	/// can be unreserved.
+	#[pallet::storage]
+	#[pallet::getter(fn serper_unbonding)]
+	pub type SerperUnbonding<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		T::AccountId,
+		(BalanceOf<T>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// The expansion rewards accumulated per unit of serper pool stake.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_share)]
+	pub type RewardPerShare<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, FixedU128, ValueQuery>;
+
+	/// The `RewardPerShare` each member's rewards were last settled at.
+	#[pallet::storage]
+	pub type RewardIndexOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, FixedU128, ValueQuery>;
+
+	/// Settled rewards waiting to be claimed by each member.
+	#[pallet::storage]
+	pub type PendingRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The native currency held in each stable currency's reserve account.
+	#[pallet::storage]
+	#[pallet::getter(fn reserve)]
+	pub type Reserves<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The fraction of a smallest unit truncated from each currency's last
+	/// applied supply change.
+	#[pallet::storage]
+	#[pallet::getter(fn supply_change_remainder)]
+	pub type SupplyChangeRemainder<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, FixedU128, ValueQuery>;
+
+	/// The accounts, such as treasury, reserve and bridge escrow accounts,
+	/// whose balances are not part of each currency's circulating supply.
+	#[pallet::storage]
+	#[pallet::getter(fn excluded_accounts)]
+	pub type ExcludedAccounts<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<T::AccountId>, ValueQuery>;
+
+	/// The collateral ratio each stable currency's contraction may not push
+	/// its reserve below.
+	#[pallet::storage]
+	#[pallet::getter(fn min_collateral_ratio)]
+	pub type MinCollateralRatio<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, FixedU128, OptionQuery>;
+
+	/// The last price of each currency a SERP round got from a price source,
+	/// and the block it was used at.
+	#[pallet::storage]
+	#[pallet::getter(fn last_good_price)]
+	pub type LastGoodPrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (Price, T::BlockNumber), OptionQuery>;
+
+	/// The source of the price the last SERP round of each currency used.
+	#[pallet::storage]
+	#[pallet::getter(fn price_source_of)]
+	pub type PriceSources<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, PriceSourceKind, OptionQuery>;
+
+	/// The native/stable liquidity pool of each stable currency.
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_pool)]
+	pub type LiquidityPools<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, LiquidityPool<BalanceOf<T>>, ValueQuery>;
+
+	/// The liquidity shares each provider holds in a pool.
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_shares)]
+	pub type LiquidityShares<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The observations at the start of each pool's current TWAP window and
+	/// at its last change.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_observations)]
+	pub type PoolObservations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		(PriceObservation<T::BlockNumber>, PriceObservation<T::BlockNumber>),
+		OptionQuery,
+	>;
+
+	/// The time-weighted average price of each pool over its last closed
+	/// TWAP window.
+	#[pallet::storage]
+	pub type PoolTwaps<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Price, OptionQuery>;
+
+	/// The terms of each peg stability module pair, keyed by stable and
+	/// collateral currency.
+	#[pallet::storage]
+	#[pallet::getter(fn psm_params)]
+	pub type PsmPairs<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		PsmParams<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The most stable currency each mint/redeem at peg window may mint or
+	/// burn per SERP round, the window being closed if `None`.
+	#[pallet::storage]
+	#[pallet::getter(fn peg_window_limit)]
+	pub type PegWindowLimits<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, OptionQuery>;
+
+	/// The stable currency minted or burnt through each mint/redeem at peg
+	/// window since the last SERP round.
+	#[pallet::storage]
+	#[pallet::getter(fn peg_window_used)]
+	pub type PegWindowUsage<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The stable currency backed by each peg stability module pair's
+	/// collateral.
+	#[pallet::storage]
+	#[pallet::getter(fn psm_debt)]
+	pub type PsmDebts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The most each currency's price may move between two SERP rounds.
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_jump)]
+	pub type MaxPriceJumps<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Permill, OptionQuery>;
+
+	/// The price of the last SERP round accepted for each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn last_serp_price)]
+	pub type LastSerpPrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Price, OptionQuery>;
+
+	/// The number of consecutive SERP rounds of each currency rejected for
+	/// a price jump.
+	#[pallet::storage]
+	#[pallet::getter(fn price_jump_rejections)]
+	pub type PriceJumpRejections<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, u32, ValueQuery>;
+
+	/// Whether each currency's SERP is paused pending governance.
+	#[pallet::storage]
+	#[pallet::getter(fn serp_paused)]
+	pub type SerpPaused<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, bool, ValueQuery>;
+
+	/// The commit–reveal schedule, oracle prices being submitted directly
+	/// if `None`.
+	#[pallet::storage]
+	#[pallet::getter(fn commit_reveal)]
+	pub type CommitReveal<T: Config> = StorageValue<_, CommitRevealSchedule<T::BlockNumber>, OptionQuery>;
+
+	/// The native currency each operator has reserved as oracle bond.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_bond)]
+	pub type OracleBonds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The unrevealed price commitment of each operator per currency.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment)]
+	pub type Commitments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, T::AccountId, PriceCommitment<T::Hash>, OptionQuery>;
+
+	/// The commitments made in each commit–reveal cycle, those still
+	/// unrevealed being slashed when the cycle ends.
+	#[pallet::storage]
+	#[pallet::getter(fn cycle_commitments)]
+	pub type CycleCommitments<T: Config> =
+		StorageMap<_, Twox64Concat, u32, Vec<(CurrencyIdOf<T>, T::AccountId)>, ValueQuery>;
+
+	/// The symbol each currency's price is listed under by the price
+	/// endpoint.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_symbol)]
+	pub type OracleSymbols<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Vec<u8>, OptionQuery>;
+
+	/// The accounts allowed to submit oracle prices, sorted.
+	#[pallet::storage]
+	#[pallet::getter(fn operators)]
+	pub type Operators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The nonce the next unsigned price submission of each operator must
+	/// carry.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_nonce)]
+	pub type OracleNonces<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
+	/// The open oracle round of each currency.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_round)]
+	pub type OracleRounds<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, u32, ValueQuery>;
+
+	/// The block the open oracle round of each currency received its first
+	/// submission at.
+	#[pallet::storage]
+	#[pallet::getter(fn round_start)]
+	pub type RoundStarts<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, T::BlockNumber, OptionQuery>;
+
+	/// The operator submissions of each currency's oracle rounds.
+	#[pallet::storage]
+	#[pallet::getter(fn round_submissions)]
+	pub type RoundSubmissions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, u32, Vec<(T::AccountId, Price)>, ValueQuery>;
+
+	/// The last aggregated oracle price of each currency and the block it was
+	/// aggregated at.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_price)]
+	pub type OraclePrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (Price, T::BlockNumber), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub operators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig { operators: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let mut operators = self.operators.clone();
+			operators.sort();
+			operators.dedup();
+			assert!(
+				operators.len() <= T::MaxOperators::get() as usize,
+				"Too many genesis oracle operators"
+			);
+			Operators::<T>::put(operators);
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::submit_prices_unsigned(payload, signature) = call {
+				if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+					return InvalidTransaction::BadProof.into();
+				}
+				Self::validate_price_payload(payload)
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// auctions filled or restarted since they were indexed are skipped
+			let expiring = AuctionExpiries::<T>::take(now);
+			for currency_id in expiring.iter() {
+				if let Some(auction) = Self::auction(currency_id).filter(|auction| auction.end < now) {
+					Auctions::<T>::remove(currency_id);
+					Self::set_contraction_debt(*currency_id, Self::contraction_debt(currency_id).saturating_add(auction.remaining));
+					Self::deposit_event(Event::AuctionClosed(*currency_id, auction.target, auction.remaining));
+				}
+			}
+
+			let expiring = expiring.len() as Weight;
+			let mut weight = T::DbWeight::get().reads_writes(
+				expiring.saturating_mul(2).saturating_add(1),
+				expiring.saturating_mul(2).saturating_add(1),
+			);
+
+			// commitments still unrevealed when their cycle ends are slashed
+			if let Some((cycle, offset)) = Self::commit_reveal_position(now) {
+				if cycle > 0 && offset.is_zero() {
+					let committed = CycleCommitments::<T>::take(cycle - 1);
+					for (currency_id, who) in committed.iter() {
+						if Commitments::<T>::take(currency_id, who).is_some() {
+							Self::slash_oracle(*currency_id, who);
+						}
+					}
+					let committed = committed.len() as Weight;
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(
+						committed.saturating_mul(3).saturating_add(2),
+						committed.saturating_mul(4).saturating_add(1),
+					));
+				}
+			}
+			weight
+		}
+
+		fn offchain_worker(now: T::BlockNumber) {
+			if !(now % T::OracleInterval::get()).is_zero() {
+				return;
+			}
+			if let Err(e) = Self::fetch_and_submit_prices() {
+				native::warn!("💸 Oracle prices not submitted: {}", e);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Transfer some balance to another account under `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_non_native_currency())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+			Ok(().into())
+		}
+
+		/// Transfer some native currency to another account.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_native_currency())]
+		pub fn transfer_native_currency(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			T::Stp258Native::transfer(&from, &to, amount)?;
+
+			Self::deposit_event(Event::Transferred(T::GetStp258NativeId::get(), from, to, amount));
+			Ok(().into())
+		}
+
+		/// Transfer some balance to another account under `currency_id`,
+		/// attaching a `memo` such as a payment reference.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_with_memo_non_native_currency())]
+		pub fn transfer_with_memo(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			memo: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::ensure_valid_memo(&memo)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+
+			Self::deposit_event(Event::TransferredWithMemo(currency_id, from, to, amount, memo));
+			Ok(().into())
+		}
+
+		/// Transfer some native currency to another account, attaching a
+		/// `memo` such as a payment reference.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_with_memo_native_currency())]
+		pub fn transfer_native_currency_with_memo(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+			memo: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::ensure_valid_memo(&memo)?;
+			T::Stp258Native::transfer(&from, &to, amount)?;
+
+			// the memo event follows the same `Transferred` as any other transfer
+			Self::deposit_event(Event::Transferred(T::GetStp258NativeId::get(), from.clone(), to.clone(), amount));
+			Self::deposit_event(Event::TransferredWithMemo(T::GetStp258NativeId::get(), from, to, amount, memo));
+			Ok(().into())
+		}
+
+		/// Select the stable currency to pay transaction fees in, or `None`
+		/// to pay them in the native currency.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::set_fee_currency())]
+		pub fn set_fee_currency(
+			origin: OriginFor<T>,
+			currency_id: Option<CurrencyIdOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			match currency_id {
+				Some(currency_id) => {
+					ensure!(
+						currency_id != T::GetStp258NativeId::get(),
+						Error::<T>::InvalidFeeCurrency
+					);
+					FeeCurrency::<T>::insert(&who, currency_id);
+				}
+				None => FeeCurrency::<T>::remove(&who),
+			}
+
+			Self::deposit_event(Event::FeeCurrencySet(who, currency_id));
+			Ok(().into())
+		}
+
+		/// Allow `who` to mint and burn `currency_id`, minting at most `cap`
+		/// in any `MintPeriod` consecutive blocks.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::grant_mint_authority())]
+		pub fn grant_mint_authority(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			cap: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			MintAuthorities::<T>::insert(
+				currency_id,
+				&who,
+				MintAuthority {
+					cap,
+					mints: Vec::new(),
+				},
+			);
+
+			Self::deposit_event(Event::MintAuthorityGranted(currency_id, who, cap));
+			Ok(().into())
+		}
+
+		/// Remove the mint authority of `who` over `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::revoke_mint_authority())]
+		pub fn revoke_mint_authority(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				MintAuthorities::<T>::contains_key(currency_id, &who),
+				Error::<T>::NoMintAuthority
+			);
+			MintAuthorities::<T>::remove(currency_id, &who);
+
+			Self::deposit_event(Event::MintAuthorityRevoked(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Mint `amount` of `currency_id` into `dest`.
+		///
+		/// The dispatch origin for this call must be `Signed` by a mint
+		/// authority of `currency_id`.
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let minter = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let mut authority = Self::mint_authority(currency_id, &minter).ok_or(Error::<T>::NoMintAuthority)?;
+
+			if let Some(cap) = authority.cap {
+				// only mints within the last `MintPeriod` blocks count against the cap
+				let now = <frame_system::Module<T>>::block_number();
+				let period = T::MintPeriod::get();
+				authority.mints.retain(|(at, _)| now.saturating_sub(*at) < period);
+				let minted = authority
+					.mints
+					.iter()
+					.fold(amount, |minted, (_, amount)| minted.saturating_add(*amount));
+				ensure!(minted <= cap, Error::<T>::MintCapExceeded);
+				match authority.mints.last_mut() {
+					Some((at, minted_at)) if *at == now => *minted_at = minted_at.saturating_add(amount),
+					_ => {
+						ensure!(
+							authority.mints.len() < T::MaxMintsPerPeriod::get() as usize,
+							Error::<T>::TooManyMints
+						);
+						authority.mints.push((now, amount));
+					}
+				}
+			}
+
+			<Self as Stp258Currency<T::AccountId>>::deposit(currency_id, &dest, amount)?;
+			MintAuthorities::<T>::insert(currency_id, &minter, authority);
+
+			Self::deposit_event(Event::Minted(currency_id, minter, dest, amount));
+			Ok(().into())
+		}
+
+		/// Burn `amount` of `currency_id` from the caller's free balance.
+		///
+		/// The dispatch origin for this call must be `Signed` by a mint
+		/// authority of `currency_id`.
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let burner = ensure_signed(origin)?;
+			ensure!(
+				MintAuthorities::<T>::contains_key(currency_id, &burner),
+				Error::<T>::NoMintAuthority
+			);
+			<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, &burner, amount)?;
+
+			Self::deposit_event(Event::Burned(currency_id, burner, amount));
+			Ok(().into())
+		}
+
+		/// Set the minimum and maximum total issuance of `currency_id`,
+		/// `None` removing the bound.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_issuance_bounds())]
+		pub fn set_issuance_bounds(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			min_issuance: Option<BalanceOf<T>>,
+			max_issuance: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let (Some(min), Some(max)) = (min_issuance, max_issuance) {
+				ensure!(min <= max, Error::<T>::InvalidIssuanceBounds);
+			}
+			MinIssuance::<T>::mutate_exists(currency_id, |bound| *bound = min_issuance);
+			MaxIssuance::<T>::mutate_exists(currency_id, |bound| *bound = max_issuance);
+
+			Self::deposit_event(Event::IssuanceBoundsSet(currency_id, min_issuance, max_issuance));
+			Ok(().into())
+		}
+
+		/// Set how expanded supply of `currency_id` is shared among
+		/// beneficiaries. The shares must sum to 100%; an empty
+		/// `distribution` removes the table.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_expansion_distribution(distribution.len() as u32))]
+		pub fn set_expansion_distribution(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			distribution: Vec<(Beneficiary<T::AccountId>, Perbill)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if distribution.is_empty() {
+				ExpansionDistribution::<T>::remove(currency_id);
+			} else {
+				ensure!(
+					distribution.len() <= T::MaxBeneficiaries::get() as usize,
+					Error::<T>::TooManyBeneficiaries
+				);
+				let total = distribution
+					.iter()
+					.map(|(_, share)| share.deconstruct() as u64)
+					.sum::<u64>();
+				ensure!(
+					total == Perbill::one().deconstruct() as u64,
+					Error::<T>::InvalidDistribution
+				);
+				ExpansionDistribution::<T>::insert(currency_id, distribution);
+			}
+
+			Self::deposit_event(Event::ExpansionDistributionSet(currency_id));
+			Ok(().into())
+		}
+
+		/// Set how the contraction of `currency_id` is executed.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_contraction_method())]
+		pub fn set_contraction_method(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			method: ContractionMethod,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			match method {
+				ContractionMethod::Bonds => {
+					ensure!(BondCurves::<T>::contains_key(currency_id), Error::<T>::BondCurveNotSet)
+				}
+				ContractionMethod::Auction => ensure!(
+					AuctionParameters::<T>::contains_key(currency_id),
+					Error::<T>::AuctionParamsNotSet
+				),
+				ContractionMethod::Stp258 | ContractionMethod::SerperPool => {}
+			}
+			ContractionMethods::<T>::insert(currency_id, method);
+
+			Self::deposit_event(Event::ContractionMethodSet(currency_id, method));
+			Ok(().into())
+		}
+
+		/// Set the bond price curve of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_bond_curve())]
+		pub fn set_bond_curve(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			curve: BondPriceCurve,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!curve.start_price.is_zero() && curve.start_price <= curve.end_price,
+				Error::<T>::InvalidBondCurve
+			);
+			BondCurves::<T>::insert(currency_id, curve);
+
+			Self::deposit_event(Event::BondCurveSet(currency_id, curve));
+			Ok(().into())
+		}
+
+		/// Burn up to `amount` of `currency_id` from the open bond window in
+		/// exchange for bonds at the current bond price, while the last
+		/// accepted SERP price of `currency_id` is below the peg.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::buy_bonds())]
+		pub fn buy_bonds(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let curve = Self::bond_curve(currency_id).ok_or(Error::<T>::BondCurveNotSet)?;
+			ensure!(
+				Self::last_serp_price(currency_id).map_or(false, |price| price < Price::one()),
+				Error::<T>::BondsNotOnSale
+			);
+			let mut window = Self::bond_window(currency_id);
+			let paid = amount.min(window.capacity.saturating_sub(window.sold));
+			ensure!(!paid.is_zero(), Error::<T>::NoBondCapacity);
+
+			let progress = Perbill::from_rational_approximation(window.sold, window.capacity);
+			let price = curve
+				.start_price
+				.saturating_add(curve.end_price.saturating_sub(curve.start_price) * progress);
+			let face_value: BalanceOf<T> = (paid.saturated_into::<u128>().saturating_mul(Perbill::ACCURACY as u128)
+				/ price.deconstruct() as u128)
+				.saturated_into();
+
+			<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, &who, paid)?;
+
+			let expires_at = <frame_system::Module<T>>::block_number().saturating_add(T::BondDuration::get());
+			BondQueueBounds::<T>::mutate(currency_id, |(_, tail)| {
+				BondQueue::<T>::insert(
+					currency_id,
+					*tail,
+					Bond {
+						owner: who.clone(),
+						amount: face_value,
+						expires_at,
+					},
+				);
+				*tail += 1;
+			});
+			AccountBonds::<T>::mutate(currency_id, &who, |bonds| *bonds = bonds.saturating_add(face_value));
+
+			window.sold = window.sold.saturating_add(paid);
+			if window.sold >= window.capacity {
+				BondWindows::<T>::remove(currency_id);
+			} else {
+				BondWindows::<T>::insert(currency_id, window);
+			}
+
+			Self::deposit_event(Event::BondsPurchased(currency_id, who, paid, face_value));
+			Self::deposit_event(Event::SerpedDownSupply(currency_id, paid));
+			Ok(().into())
+		}
+
+		/// Set the contraction auction parameters of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_auction_params())]
+		pub fn set_auction_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			params: AuctionParams<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!params.duration.is_zero() && params.max_discount < Perbill::one(),
+				Error::<T>::InvalidAuctionParams
+			);
+			AuctionParameters::<T>::insert(currency_id, params);
+
+			Self::deposit_event(Event::AuctionParamsSet(currency_id));
+			Ok(().into())
+		}
+
+		/// Offer up to `amount` of `currency_id` to its contraction auction
+		/// for native currency at the current auction price. The offer is
+		/// filled up to what remains of the auction.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut auction = Self::auction(currency_id).ok_or(Error::<T>::AuctionNotOpen)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now <= auction.end, Error::<T>::AuctionNotOpen);
+
+			let filled = amount.min(auction.remaining);
+			let price = Self::auction_price(currency_id, &auction, now).ok_or(Error::<T>::AuctionParamsNotSet)?;
+			let native_base_unit = Self::base_unit(T::GetStp258NativeId::get()).saturated_into::<u128>();
+			let native_amount: BalanceOf<T> = (filled.saturated_into::<u128>().saturating_mul(native_base_unit)
+				/ price.max(1))
+			.saturated_into();
+			ensure!(!native_amount.is_zero(), Error::<T>::BidTooSmall);
+
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, &who, filled)?;
+				Self::pay_from_reserve(currency_id, &who, native_amount)
+			})?;
+			Self::deposit_event(Event::AuctionBid(currency_id, who, filled, native_amount));
+			Self::deposit_event(Event::SerpedDownSupply(currency_id, filled));
+
+			auction.remaining -= filled;
+			if auction.remaining.is_zero() {
+				Auctions::<T>::remove(currency_id);
+				Self::deposit_event(Event::AuctionClosed(currency_id, auction.target, Zero::zero()));
+			} else {
+				Auctions::<T>::insert(currency_id, auction);
+			}
+			Ok(().into())
+		}
+
+		/// Reserve `amount` of `currency_id` into its serper pool.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::join_serper_pool())]
+		pub fn join_serper_pool(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InsufficientSerperStake);
+			Self::settle_rewards(currency_id, &who);
+			let mut pool = Self::serper_pool(currency_id);
+			if !SerperStakes::<T>::contains_key(currency_id, &who) {
+				ensure!(pool.members < T::MaxSerperPoolMembers::get(), Error::<T>::SerperPoolFull);
+				pool.members += 1;
+			}
+			<Self as Stp258CurrencyReservable<T::AccountId>>::reserve(currency_id, &who, amount)?;
+
+			SerperStakes::<T>::mutate(currency_id, &who, |stake| *stake = stake.saturating_add(amount));
+			pool.total = pool.total.saturating_add(amount);
+			SerperPools::<T>::insert(currency_id, pool);
+
+			Self::deposit_event(Event::SerperPoolJoined(currency_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Take `amount` of `currency_id` out of its serper pool. It stays
+		/// reserved until `SerperCooldown` has passed.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::leave_serper_pool())]
+		pub fn leave_serper_pool(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stake = Self::serper_stake(currency_id, &who);
+			ensure!(!amount.is_zero() && amount <= stake, Error::<T>::InsufficientSerperStake);
+			Self::settle_rewards(currency_id, &who);
+
+			let mut pool = Self::serper_pool(currency_id);
+			pool.total = pool.total.saturating_sub(amount);
+			if amount == stake {
+				SerperStakes::<T>::remove(currency_id, &who);
+				pool.members = pool.members.saturating_sub(1);
+			} else {
+				SerperStakes::<T>::insert(currency_id, &who, stake - amount);
+			}
+			SerperPools::<T>::insert(currency_id, pool);
+
+			let unlock_at = <frame_system::Module<T>>::block_number().saturating_add(T::SerperCooldown::get());
+			SerperUnbonding::<T>::mutate(currency_id, &who, |unbonding| {
+				let unbonding_amount = unbonding.map_or(amount, |(unbonding_amount, _)| unbonding_amount.saturating_add(amount));
+				*unbonding = Some((unbonding_amount, unlock_at));
+			});
+
+			Self::deposit_event(Event::SerperPoolLeft(currency_id, who, amount, unlock_at));
+			Ok(().into())
+		}
+
+		/// Unreserve the stable currency that left the serper pool once its
+		/// cooldown has passed.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (amount, unlock_at) = Self::serper_unbonding(currency_id, &who).ok_or(Error::<T>::NothingToWithdraw)?;
+			ensure!(
+				<frame_system::Module<T>>::block_number() >= unlock_at,
+				Error::<T>::SerperCooldownActive
+			);
+
+			SerperUnbonding::<T>::remove(currency_id, &who);
+			<Self as Stp258CurrencyReservable<T::AccountId>>::unreserve(currency_id, &who, amount);
+
+			Self::deposit_event(Event::SerperWithdrawn(currency_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Claim the expansion rewards earned by the caller's serper pool
+		/// stake in `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::settle_rewards(currency_id, &who);
+			let amount = PendingRewards::<T>::get(currency_id, &who);
+			ensure!(!amount.is_zero(), Error::<T>::NoRewards);
+
+			T::Stp258Currency::transfer(currency_id, &T::SerperPoolAccount::get(), &who, amount)?;
+			PendingRewards::<T>::remove(currency_id, &who);
+
+			Self::deposit_event(Event::RewardsClaimed(currency_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Move `amount` of native currency into the reserve backing
+		/// `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::fund_reserve())]
+		pub fn fund_reserve(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			T::Stp258Native::transfer(&who, &Self::reserve_account(currency_id), amount)?;
+			Reserves::<T>::mutate(currency_id, |reserve| *reserve = reserve.saturating_add(amount));
+
+			Self::deposit_event(Event::ReserveFunded(currency_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Set the minimum collateral ratio of `currency_id`, `None` removing
+		/// it.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_min_collateral_ratio())]
+		pub fn set_min_collateral_ratio(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			ratio: Option<FixedU128>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MinCollateralRatio::<T>::mutate_exists(currency_id, |min_ratio| *min_ratio = ratio);
+
+			Self::deposit_event(Event::MinCollateralRatioSet(currency_id, ratio));
+			Ok(().into())
+		}
+
+		/// Exclude the balance of `who` from the circulating supply of
+		/// `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::exclude_from_circulation())]
+		pub fn exclude_from_circulation(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ExcludedAccounts::<T>::try_mutate(currency_id, |accounts| -> DispatchResult {
+				ensure!(!accounts.contains(&who), Error::<T>::AlreadyExcluded);
+				ensure!(
+					accounts.len() < T::MaxExcludedAccounts::get() as usize,
+					Error::<T>::TooManyExcludedAccounts
+				);
+				accounts.push(who.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ExcludedFromCirculation(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Count the balance of `who` in the circulating supply of
+		/// `currency_id` again.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::include_in_circulation())]
+		pub fn include_in_circulation(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ExcludedAccounts::<T>::try_mutate_exists(currency_id, |maybe_accounts| -> DispatchResult {
+				let accounts = maybe_accounts.as_mut().ok_or(Error::<T>::NotExcluded)?;
+				let index = accounts.iter().position(|account| *account == who).ok_or(Error::<T>::NotExcluded)?;
+				accounts.swap_remove(index);
+				if accounts.is_empty() {
+					*maybe_accounts = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::IncludedInCirculation(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Set the symbol `currency_id` is listed under by the price endpoint,
+		/// `None` removing it from the oracle.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_oracle_symbol(T::MaxOracleSymbols::get()))]
+		pub fn set_oracle_symbol(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			symbol: Option<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(symbol) = &symbol {
+				ensure!(symbol.len() <= T::MaxSymbolLength::get() as usize, Error::<T>::SymbolTooLong);
+				ensure!(
+					OracleSymbols::<T>::contains_key(currency_id)
+						|| (OracleSymbols::<T>::iter().count() as u32) < T::MaxOracleSymbols::get(),
+					Error::<T>::TooManyOracleSymbols
+				);
+			}
+			OracleSymbols::<T>::mutate_exists(currency_id, |maybe_symbol| *maybe_symbol = symbol.clone());
+
+			Self::deposit_event(Event::OracleSymbolSet(currency_id, symbol));
+			Ok(().into())
+		}
+
+		/// Set the most the price of `currency_id` may move between two SERP
+		/// rounds, `None` removing the limit.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_max_price_jump())]
+		pub fn set_max_price_jump(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			max_jump: Option<Permill>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MaxPriceJumps::<T>::mutate_exists(currency_id, |jump| *jump = max_jump);
+
+			Self::deposit_event(Event::MaxPriceJumpSet(currency_id, max_jump));
+			Ok(().into())
+		}
+
+		/// Resume the SERP of `currency_id`. The next round's price is
+		/// accepted whatever the last one was.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_serp())]
+		pub fn unpause_serp(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::serp_paused(currency_id), Error::<T>::SerpNotPaused);
+			SerpPaused::<T>::remove(currency_id);
+			PriceJumpRejections::<T>::remove(currency_id);
+			LastSerpPrices::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::SerpUnpaused(currency_id));
+			Ok(().into())
+		}
+
+		/// Enable commit–reveal oracle rounds of `commit_period` and
+		/// `reveal_period` blocks from now, or disable them with `None`.
+		/// Commitments of the current cycle can no longer be revealed and are
+		/// dropped without slashing.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_commit_reveal(T::MaxCommitmentsPerCycle::get()))]
+		pub fn set_commit_reveal(
+			origin: OriginFor<T>,
+			periods: Option<(T::BlockNumber, T::BlockNumber)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some((commit_period, reveal_period)) = periods {
+				ensure!(
+					!commit_period.is_zero() && !reveal_period.is_zero(),
+					Error::<T>::InvalidCommitRevealPeriods
+				);
+			}
+			let now = <frame_system::Module<T>>::block_number();
+			// earlier cycles were settled when they ended
+			if let Some((cycle, _)) = Self::commit_reveal_position(now) {
+				for (currency_id, who) in CycleCommitments::<T>::take(cycle) {
+					Commitments::<T>::remove(currency_id, &who);
+				}
+			}
+			match periods {
+				Some((commit_period, reveal_period)) => CommitReveal::<T>::put(CommitRevealSchedule {
+					commit_period,
+					reveal_period,
+					start: now,
+				}),
+				None => CommitReveal::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::CommitRevealSet(periods));
+			Ok(().into())
+		}
+
+		/// Reserve native currency to take part in commit–reveal rounds,
+		/// topping the caller's oracle bond up to `OracleBond`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::bond_oracle())]
+		pub fn bond_oracle(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let bond = Self::oracle_bond(&who);
+			ensure!(bond < T::OracleBond::get(), Error::<T>::AlreadyBonded);
+			let amount = T::OracleBond::get() - bond;
+			<Self as Stp258CurrencyReservable<T::AccountId>>::reserve(T::GetStp258NativeId::get(), &who, amount)?;
+			OracleBonds::<T>::insert(&who, T::OracleBond::get());
+
+			Self::deposit_event(Event::OracleBonded(who, amount));
+			Ok(().into())
+		}
+
+		/// Release what is left of the caller's oracle bond.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::unbond_oracle(T::MaxCommitmentsPerCycle::get()))]
+		pub fn unbond_oracle(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if let Some((cycle, _)) = Self::commit_reveal_position(<frame_system::Module<T>>::block_number()) {
+				ensure!(
+					!Self::cycle_commitments(cycle).iter().any(|(currency_id, operator)| {
+						*operator == who && Commitments::<T>::contains_key(currency_id, &who)
+					}),
+					Error::<T>::CommitmentsOutstanding
+				);
+			}
+			let amount = OracleBonds::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::OracleNotBonded);
+			<Self as Stp258CurrencyReservable<T::AccountId>>::unreserve(T::GetStp258NativeId::get(), &who, amount);
+
+			Self::deposit_event(Event::OracleUnbonded(who, amount));
+			Ok(().into())
+		}
+
+		/// Commit to `hash(who, currency_id, cycle, price, salt)` during the
+		/// commit window of the current cycle.
+		///
+		/// The dispatch origin for this call must be `Signed` by a bonded
+		/// oracle operator.
+		#[pallet::weight(T::WeightInfo::commit_price())]
+		pub fn commit_price(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::operators().binary_search(&who).is_ok(), Error::<T>::NotOperator);
+			ensure!(OracleSymbols::<T>::contains_key(currency_id), Error::<T>::UnknownOracleCurrency);
+			ensure!(Self::oracle_bond(&who) >= T::OracleBond::get(), Error::<T>::OracleNotBonded);
+			let (cycle, offset) = Self::commit_reveal_position(<frame_system::Module<T>>::block_number())
+				.ok_or(Error::<T>::CommitRevealInactive)?;
+			ensure!(offset < Self::commit_period(), Error::<T>::NotCommitPhase);
+			ensure!(!Commitments::<T>::contains_key(currency_id, &who), Error::<T>::AlreadyCommitted);
+			ensure!(
+				CycleCommitments::<T>::decode_len(cycle).unwrap_or(0) < T::MaxCommitmentsPerCycle::get() as usize,
+				Error::<T>::TooManyCommitments
+			);
+			Commitments::<T>::insert(currency_id, &who, PriceCommitment { cycle, hash });
+			CycleCommitments::<T>::append(cycle, (currency_id, who.clone()));
+
+			Self::deposit_event(Event::PriceCommitted(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Reveal the price committed for `currency_id` during the reveal
+		/// window of the cycle it was committed in. A reveal not matching the
+		/// commitment is slashed.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// committing operator.
+		#[pallet::weight(T::WeightInfo::reveal_price())]
+		pub fn reveal_price(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			price: Price,
+			salt: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (cycle, offset) = Self::commit_reveal_position(<frame_system::Module<T>>::block_number())
+				.ok_or(Error::<T>::CommitRevealInactive)?;
+			ensure!(offset >= Self::commit_period(), Error::<T>::NotRevealPhase);
+			let commitment = Self::commitment(currency_id, &who)
+				.filter(|commitment| commitment.cycle == cycle)
+				.ok_or(Error::<T>::NoCommitment)?;
+			Commitments::<T>::remove(currency_id, &who);
+
+			if T::Hashing::hash_of(&(&who, currency_id, cycle, price, salt)) != commitment.hash || price.is_zero() {
+				Self::slash_oracle(currency_id, &who);
+				return Ok(().into());
+			}
+			Self::deposit_event(Event::PriceRevealed(currency_id, who.clone(), price));
+			Self::submit_round_price(currency_id, &who, price);
+			Ok(().into())
+		}
+
+		/// Add `who` to the oracle operators.
+		///
+		/// The dispatch origin of this call must be `OperatorOrigin`.
+		#[pallet::weight(T::WeightInfo::add_operator())]
+		pub fn add_operator(origin: OriginFor<T>, who: <T::Lookup as StaticLookup>::Source) -> DispatchResultWithPostInfo {
+			T::OperatorOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Operators::<T>::try_mutate(|operators| -> DispatchResult {
+				let index = operators.binary_search(&who).err().ok_or(Error::<T>::AlreadyOperator)?;
+				ensure!(
+					operators.len() < T::MaxOperators::get() as usize,
+					Error::<T>::TooManyOperators
+				);
+				operators.insert(index, who.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OperatorAdded(who));
+			Ok(().into())
+		}
+
+		/// Remove `who` from the oracle operators, dropping their submissions
+		/// to open rounds.
+		///
+		/// The dispatch origin of this call must be `OperatorOrigin`.
+		#[pallet::weight(T::WeightInfo::remove_operator(T::MaxOracleSymbols::get()))]
+		pub fn remove_operator(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::OperatorOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Operators::<T>::try_mutate(|operators| -> DispatchResult {
+				let index = operators.binary_search(&who).ok().ok_or(Error::<T>::NotOperator)?;
+				operators.remove(index);
+				Ok(())
+			})?;
+			Self::purge_submissions(&who);
+
+			Self::deposit_event(Event::OperatorRemoved(who));
+			Ok(().into())
+		}
+
+		/// Replace the oracle operator `remove` with `add`.
+		///
+		/// The dispatch origin of this call must be `OperatorOrigin`.
+		#[pallet::weight(T::WeightInfo::swap_operator(T::MaxOracleSymbols::get()))]
+		pub fn swap_operator(
+			origin: OriginFor<T>,
+			remove: <T::Lookup as StaticLookup>::Source,
+			add: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::OperatorOrigin::ensure_origin(origin)?;
+			let remove = T::Lookup::lookup(remove)?;
+			let add = T::Lookup::lookup(add)?;
+			if remove == add {
+				return Ok(().into());
+			}
+			Operators::<T>::try_mutate(|operators| -> DispatchResult {
+				let index = operators.binary_search(&remove).ok().ok_or(Error::<T>::NotOperator)?;
+				ensure!(operators.binary_search(&add).is_err(), Error::<T>::AlreadyOperator);
+				operators[index] = add.clone();
+				operators.sort();
+				Ok(())
+			})?;
+			Self::purge_submissions(&remove);
+
+			Self::deposit_event(Event::OperatorSwapped(remove, add));
+			Ok(().into())
+		}
+
+		/// Submit oracle prices, each for one whole unit of its currency.
+		///
+		/// The dispatch origin for this call must be `Signed` by an oracle
+		/// operator.
+		#[pallet::weight(T::WeightInfo::submit_prices(prices.len() as u32))]
+		pub fn submit_prices(
+			origin: OriginFor<T>,
+			prices: Vec<(CurrencyIdOf<T>, Price)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_submit_prices(who, prices)?;
+			Ok(().into())
+		}
+
+		/// Submit oracle prices signed by an operator's oracle key, without
+		/// paying a fee. The payload is checked by `validate_unsigned`.
+		///
+		/// The dispatch origin for this call must be `None`.
+		#[pallet::weight(T::WeightInfo::submit_prices_unsigned(payload.prices.len() as u32))]
+		pub fn submit_prices_unsigned(
+			origin: OriginFor<T>,
+			payload: PricePayload<T::Public, T::BlockNumber, CurrencyIdOf<T>>,
+			_signature: T::Signature,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let who = payload.public.into_account();
+			// a failed submission leaves the nonce for the next one
+			with_transaction_result(|| {
+				Self::do_submit_prices(who.clone(), payload.prices)?;
+				OracleNonces::<T>::mutate(&who, |nonce| *nonce = nonce.saturating_add(1));
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
+		/// Add liquidity to the native/`currency_id` pool: `native_amount` of
+		/// native currency and at most `max_stable_amount` of `currency_id`
+		/// at the pool's rate. The first provider sets the rate.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] native_amount: BalanceOf<T>,
+			#[pallet::compact] max_stable_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(currency_id != T::GetStp258NativeId::get(), Error::<T>::InvalidPoolCurrency);
+			ensure!(
+				!native_amount.is_zero() && !max_stable_amount.is_zero(),
+				Error::<T>::InvalidPoolAmount
+			);
+			let mut pool = Self::liquidity_pool(currency_id);
+			let (native_in, stable_in, shares) = if pool.shares.is_zero() {
+				(native_amount, max_stable_amount, native_amount)
+			} else {
+				let total = pool.shares.saturated_into::<u128>();
+				let shares = multiply_by_rational(native_amount.saturated_into(), total, pool.native.saturated_into())
+					.map_err(|_| Error::<T>::InsufficientPoolLiquidity)?;
+				let native_in = Self::mul_div_ceil(shares, pool.native.saturated_into(), total)
+					.ok_or(Error::<T>::InsufficientPoolLiquidity)?;
+				let stable_in = Self::mul_div_ceil(shares, pool.stable.saturated_into(), total)
+					.ok_or(Error::<T>::InsufficientPoolLiquidity)?;
+				(native_in.saturated_into(), stable_in.saturated_into(), shares.saturated_into())
+			};
+			ensure!(!shares.is_zero(), Error::<T>::InvalidPoolAmount);
+			ensure!(stable_in <= max_stable_amount, Error::<T>::PoolSlippageExceeded);
+
+			let pool_account = Self::pool_account(currency_id);
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::transfer(T::GetStp258NativeId::get(), &who, &pool_account, native_in)?;
+				<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &who, &pool_account, stable_in)
+			})?;
+			Self::update_pool_observations(currency_id);
+			pool.native = pool.native.saturating_add(native_in);
+			pool.stable = pool.stable.saturating_add(stable_in);
+			pool.shares = pool.shares.saturating_add(shares);
+			LiquidityPools::<T>::insert(currency_id, pool);
+			LiquidityShares::<T>::mutate(currency_id, &who, |held| *held = held.saturating_add(shares));
+
+			Self::deposit_event(Event::LiquidityAdded(currency_id, who, native_in, stable_in, shares));
+			Ok(().into())
+		}
+
+		/// Redeem `shares` of the native/`currency_id` pool for their part of
+		/// its reserves.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] shares: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let held = Self::liquidity_shares(currency_id, &who);
+			ensure!(!shares.is_zero() && shares <= held, Error::<T>::InsufficientLiquidityShares);
+			let mut pool = Self::liquidity_pool(currency_id);
+			let total = pool.shares.saturated_into::<u128>();
+			let native_out: BalanceOf<T> =
+				multiply_by_rational(shares.saturated_into(), pool.native.saturated_into(), total)
+					.map_err(|_| Error::<T>::InsufficientPoolLiquidity)?
+					.saturated_into();
+			let stable_out: BalanceOf<T> =
+				multiply_by_rational(shares.saturated_into(), pool.stable.saturated_into(), total)
+					.map_err(|_| Error::<T>::InsufficientPoolLiquidity)?
+					.saturated_into();
+
+			let pool_account = Self::pool_account(currency_id);
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::transfer(T::GetStp258NativeId::get(), &pool_account, &who, native_out)?;
+				<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &pool_account, &who, stable_out)
+			})?;
+			Self::update_pool_observations(currency_id);
+			pool.native = pool.native.saturating_sub(native_out);
+			pool.stable = pool.stable.saturating_sub(stable_out);
+			pool.shares = pool.shares.saturating_sub(shares);
+			LiquidityPools::<T>::insert(currency_id, pool);
+			if shares == held {
+				LiquidityShares::<T>::remove(currency_id, &who);
+			} else {
+				LiquidityShares::<T>::insert(currency_id, &who, held - shares);
+			}
+
+			Self::deposit_event(Event::LiquidityRemoved(currency_id, who, native_out, stable_out, shares));
+			Ok(().into())
+		}
+
+		/// Swap `supply_amount` of `supply_currency_id` for at least
+		/// `min_target_amount` of `target_currency_id` through their
+		/// liquidity pool, less `PoolSwapFee` of the supplied amount.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::pool_swap())]
+		pub fn pool_swap(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] supply_amount: BalanceOf<T>,
+			target_currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] min_target_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let native_currency_id = T::GetStp258NativeId::get();
+			let (currency_id, supply_native) = match (supply_currency_id == native_currency_id, target_currency_id == native_currency_id) {
+				(true, false) => (target_currency_id, true),
+				(false, true) => (supply_currency_id, false),
+				_ => return Err(Error::<T>::InvalidPoolCurrency.into()),
+			};
+			ensure!(!supply_amount.is_zero(), Error::<T>::InvalidPoolAmount);
+			let mut pool = Self::liquidity_pool(currency_id);
+			let (supply_reserve, target_reserve) = if supply_native {
+				(pool.native, pool.stable)
+			} else {
+				(pool.stable, pool.native)
+			};
+			ensure!(!target_reserve.is_zero(), Error::<T>::InsufficientPoolLiquidity);
+
+			let supply = supply_amount.saturated_into::<u128>();
+			let supply_after_fee = supply.saturating_sub(T::PoolSwapFee::get().mul_ceil(supply));
+			let target_amount: BalanceOf<T> = multiply_by_rational(
+				supply_after_fee,
+				target_reserve.saturated_into(),
+				supply_reserve.saturated_into::<u128>().saturating_add(supply_after_fee),
+			)
+			.map_err(|_| Error::<T>::InsufficientPoolLiquidity)?
+			.saturated_into();
+			ensure!(!target_amount.is_zero(), Error::<T>::InsufficientPoolLiquidity);
+			ensure!(target_amount >= min_target_amount, Error::<T>::PoolSlippageExceeded);
+
+			let pool_account = Self::pool_account(currency_id);
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::transfer(supply_currency_id, &who, &pool_account, supply_amount)?;
+				<Self as Stp258Currency<T::AccountId>>::transfer(target_currency_id, &pool_account, &who, target_amount)
+			})?;
+			Self::update_pool_observations(currency_id);
+			if supply_native {
+				pool.native = pool.native.saturating_add(supply_amount);
+				pool.stable = pool.stable.saturating_sub(target_amount);
+			} else {
+				pool.stable = pool.stable.saturating_add(supply_amount);
+				pool.native = pool.native.saturating_sub(target_amount);
+			}
+			LiquidityPools::<T>::insert(currency_id, pool);
+
+			Self::deposit_event(Event::PoolSwapped(
+				who,
+				supply_currency_id,
+				supply_amount,
+				target_currency_id,
+				target_amount,
+			));
+			Ok(().into())
+		}
+
+		/// Set the terms on which `stable_currency_id` is swapped for
+		/// `collateral_currency_id` at the peg. A zero debt ceiling stops
+		/// minting.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_psm_params())]
+		pub fn set_psm_params(
+			origin: OriginFor<T>,
+			stable_currency_id: CurrencyIdOf<T>,
+			collateral_currency_id: CurrencyIdOf<T>,
+			params: PsmParams<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let native_currency_id = T::GetStp258NativeId::get();
+			ensure!(
+				stable_currency_id != native_currency_id
+					&& collateral_currency_id != native_currency_id
+					&& stable_currency_id != collateral_currency_id,
+				Error::<T>::InvalidPsmPair
+			);
+			PsmPairs::<T>::insert(stable_currency_id, collateral_currency_id, params);
+
+			Self::deposit_event(Event::PsmParamsSet(stable_currency_id, collateral_currency_id, params));
+			Ok(().into())
+		}
+
+		/// Swap `collateral_amount` of `collateral_currency_id` for
+		/// `stable_currency_id` at the peg, less the pair's mint fee.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::psm_mint())]
+		pub fn psm_mint(
+			origin: OriginFor<T>,
+			stable_currency_id: CurrencyIdOf<T>,
+			collateral_currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] collateral_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let params = Self::psm_params(stable_currency_id, collateral_currency_id).ok_or(Error::<T>::PsmPairNotFound)?;
+			let stable_amount = Self::at_peg(collateral_currency_id, stable_currency_id, collateral_amount)
+				.ok_or(Error::<T>::PegConversionOverflow)?;
+			let fee = params.mint_fee.mul_ceil(stable_amount);
+			let minted = stable_amount.saturating_sub(fee);
+			// the fee stays in the module as surplus collateral
+			let debt = Self::psm_debt(stable_currency_id, collateral_currency_id).saturating_add(minted);
+			ensure!(debt <= params.debt_ceiling, Error::<T>::PsmDebtCeilingExceeded);
+
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::transfer(
+					collateral_currency_id,
+					&who,
+					&Self::psm_account(),
+					collateral_amount,
+				)?;
+				<Self as Stp258Currency<T::AccountId>>::deposit(stable_currency_id, &who, minted)
+			})?;
+			PsmDebts::<T>::insert(stable_currency_id, collateral_currency_id, debt);
+
+			Self::deposit_event(Event::PsmMinted(
+				stable_currency_id,
+				collateral_currency_id,
+				who,
+				collateral_amount,
+				minted,
+				fee,
+			));
+			Ok(().into())
+		}
+
+		/// Swap `stable_amount` of `stable_currency_id` for
+		/// `collateral_currency_id` at the peg, less the pair's redeem fee.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::psm_redeem())]
+		pub fn psm_redeem(
+			origin: OriginFor<T>,
+			stable_currency_id: CurrencyIdOf<T>,
+			collateral_currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] stable_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let params = Self::psm_params(stable_currency_id, collateral_currency_id).ok_or(Error::<T>::PsmPairNotFound)?;
+			let debt = Self::psm_debt(stable_currency_id, collateral_currency_id);
+			ensure!(stable_amount <= debt, Error::<T>::InsufficientPsmCollateral);
+			let collateral_amount = Self::at_peg(stable_currency_id, collateral_currency_id, stable_amount)
+				.ok_or(Error::<T>::PegConversionOverflow)?;
+			let fee = params.redeem_fee.mul_ceil(collateral_amount);
+			let redeemed = collateral_amount.saturating_sub(fee);
+
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::withdraw(stable_currency_id, &who, stable_amount)?;
+				<Self as Stp258Currency<T::AccountId>>::transfer(
+					collateral_currency_id,
+					&Self::psm_account(),
+					&who,
+					redeemed,
+				)
+			})?;
+			PsmDebts::<T>::insert(stable_currency_id, collateral_currency_id, debt - stable_amount);
+
+			Self::deposit_event(Event::PsmRedeemed(
+				stable_currency_id,
+				collateral_currency_id,
+				who,
+				stable_amount,
+				redeemed,
+				fee,
+			));
+			Ok(().into())
+		}
+
+		/// Set the most of `currency_id` that may be minted or burnt at the
+		/// peg per SERP round, `None` closing the window. The window only
+		/// checks the reserve of a currency given a minimum collateral ratio.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_peg_window_limit())]
+		pub fn set_peg_window_limit(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			limit: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(currency_id != T::GetStp258NativeId::get(), Error::<T>::InvalidPegWindowCurrency);
+			PegWindowLimits::<T>::mutate_exists(currency_id, |maybe_limit| *maybe_limit = limit);
+
+			Self::deposit_event(Event::PegWindowLimitSet(currency_id, limit));
+			Ok(().into())
+		}
+
+		/// Burn `native_amount` of native currency for `currency_id` at the
+		/// peg while `currency_id` trades above it.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::mint_at_peg())]
+		pub fn mint_at_peg(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] native_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (stable_price, native_price) = Self::peg_window_prices(currency_id)?;
+			ensure!(stable_price > Price::one(), Error::<T>::NotAbovePeg);
+			let stable_amount: BalanceOf<T> = Price::checked_from_rational(
+				native_amount.saturated_into::<u128>(),
+				Self::base_unit(T::GetStp258NativeId::get()).saturated_into::<u128>(),
+			)
+			.and_then(|native| native.checked_mul(&native_price))
+			.and_then(|value| value.checked_mul_int(Self::base_unit(currency_id).saturated_into::<u128>()))
+			.ok_or(Error::<T>::InvalidQuotePrice)?
+			.saturated_into();
+			let used = Self::use_peg_window(currency_id, stable_amount)?;
+
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::withdraw(T::GetStp258NativeId::get(), &who, native_amount)?;
+				<Self as Stp258Currency<T::AccountId>>::deposit(currency_id, &who, stable_amount)
+			})?;
+			PegWindowUsage::<T>::insert(currency_id, used);
+
+			Self::deposit_event(Event::MintedAtPeg(currency_id, who, native_amount, stable_amount));
+			Ok(().into())
+		}
+
+		/// Burn `stable_amount` of `currency_id` for native currency at the
+		/// peg while `currency_id` trades below it.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::redeem_at_peg())]
+		pub fn redeem_at_peg(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] stable_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (stable_price, native_price) = Self::peg_window_prices(currency_id)?;
+			ensure!(stable_price < Price::one(), Error::<T>::NotBelowPeg);
+			let native_amount: BalanceOf<T> = Price::checked_from_rational(
+				stable_amount.saturated_into::<u128>(),
+				Self::base_unit(currency_id).saturated_into::<u128>(),
+			)
+			.and_then(|value| value.checked_div(&native_price))
+			.and_then(|native| native.checked_mul_int(Self::base_unit(T::GetStp258NativeId::get()).saturated_into::<u128>()))
+			.ok_or(Error::<T>::InvalidQuotePrice)?
+			.saturated_into();
+			let used = Self::use_peg_window(currency_id, stable_amount)?;
+
+			with_transaction_result(|| {
+				<Self as Stp258Currency<T::AccountId>>::withdraw(currency_id, &who, stable_amount)?;
+				<Self as Stp258Currency<T::AccountId>>::deposit(T::GetStp258NativeId::get(), &who, native_amount)
+			})?;
+			PegWindowUsage::<T>::insert(currency_id, used);
+
+			Self::deposit_event(Event::RedeemedAtPeg(currency_id, who, stable_amount, native_amount));
+			Ok(().into())
+		}
+
+		/// update amount of account `who` under `currency_id`.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::update_balance_non_native_currency())]
+		pub fn update_balance(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			amount: AmountOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let dest = T::Lookup::lookup(who)?;
+			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether a SERP round of `currency_id` at `price` may go ahead.
+	///
+	/// A price further than `MaxPriceJumps` from the last accepted one skips
+	/// the round, and `MaxPriceJumpRejections` such rounds in a row pause the
+	/// currency's SERP.
+	fn accept_serp_price(currency_id: CurrencyIdOf<T>, price: Price) -> bool {
+		if Self::serp_paused(currency_id) {
+			native::info!("💸 The SERP of ({:?}) is paused.", currency_id);
+			return false;
+		}
+
+		if let (Some(max_jump), Some(last_price)) =
+			(Self::max_price_jump(currency_id), Self::last_serp_price(currency_id))
+		{
+			if Self::exceeds_deviation(price, last_price, max_jump) {
+				Self::deposit_event(Event::PriceJumpRejected(currency_id, last_price, price));
+				let rejections = PriceJumpRejections::<T>::mutate(currency_id, |rejections| {
+					*rejections = rejections.saturating_add(1);
+					*rejections
+				});
+				if rejections >= T::MaxPriceJumpRejections::get() {
+					SerpPaused::<T>::insert(currency_id, true);
+					Self::deposit_event(Event::SerpPaused(currency_id));
+				}
+				return false;
+			}
+		}
+
+		LastSerpPrices::<T>::insert(currency_id, price);
+		PriceJumpRejections::<T>::remove(currency_id);
+		true
+	}
+
+	/// Ensure the SERP of `currency_id` is not paused.
+	fn ensure_serp_active(currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		ensure!(!Self::serp_paused(currency_id), Error::<T>::SerpPaused);
+		Ok(())
+	}
+
+	/// Drop the submissions of `who` to the open oracle rounds, one round
+	/// per currency with an oracle symbol, of which there are at most
+	/// `MaxOracleSymbols`.
+	fn purge_submissions(who: &T::AccountId) {
+		for currency_id in OracleSymbols::<T>::iter().map(|(currency_id, _)| currency_id).collect::<Vec<_>>() {
+			let round = Self::oracle_round(currency_id);
+			RoundSubmissions::<T>::mutate_exists(currency_id, round, |maybe_submissions| {
+				if let Some(submissions) = maybe_submissions {
+					submissions.retain(|(operator, _)| operator != who);
+					if submissions.is_empty() {
+						*maybe_submissions = None;
+						RoundStarts::<T>::remove(currency_id);
+					}
+				}
+			});
+		}
+	}
+
+	/// The cycle of the commit–reveal schedule at `now`, and how many
+	/// blocks into it `now` is. `None` if commit–reveal rounds are disabled.
+	fn commit_reveal_position(now: T::BlockNumber) -> Option<(u32, T::BlockNumber)> {
+		let schedule = Self::commit_reveal()?;
+		let period = schedule.commit_period.saturating_add(schedule.reveal_period);
+		let elapsed = now.saturating_sub(schedule.start);
+		Some(((elapsed / period).saturated_into(), elapsed % period))
+	}
+
+	fn commit_period() -> T::BlockNumber {
+		Self::commit_reveal().map(|schedule| schedule.commit_period).unwrap_or_else(Zero::zero)
+	}
+
+	/// Slash `OracleSlash` from the oracle bond of `who`.
+	fn slash_oracle(currency_id: CurrencyIdOf<T>, who: &T::AccountId) {
+		let amount = T::OracleSlash::get().min(Self::oracle_bond(who));
+		let gap =
+			<Self as Stp258CurrencyReservable<T::AccountId>>::slash_reserved(T::GetStp258NativeId::get(), who, amount);
+		let slashed = amount - gap;
+		OracleBonds::<T>::mutate_exists(who, |maybe_bond| {
+			let bond = maybe_bond.unwrap_or_else(Zero::zero).saturating_sub(slashed);
+			*maybe_bond = if bond.is_zero() { None } else { Some(bond) };
+		});
+		Self::deposit_event(Event::OracleSlashed(currency_id, who.clone(), slashed));
+	}
+
+	/// Record `prices` submitted by the operator `who`.
+	fn do_submit_prices(who: T::AccountId, prices: Vec<(CurrencyIdOf<T>, Price)>) -> DispatchResult {
+		ensure!(Self::commit_reveal().is_none(), Error::<T>::CommitRevealActive);
+		ensure!(Self::operators().binary_search(&who).is_ok(), Error::<T>::NotOperator);
+		Self::ensure_valid_prices(&prices)?;
+
+		for (currency_id, price) in prices {
+			Self::deposit_event(Event::PriceSubmitted(currency_id, who.clone(), price));
+			Self::submit_round_price(currency_id, &who, price);
+		}
+		Ok(())
+	}
+
+	/// Ensure every price is non-zero and for a currency with an oracle
+	/// symbol.
+	fn ensure_valid_prices(prices: &[(CurrencyIdOf<T>, Price)]) -> DispatchResult {
+		for (currency_id, price) in prices.iter() {
+			ensure!(OracleSymbols::<T>::contains_key(currency_id), Error::<T>::UnknownOracleCurrency);
+			ensure!(!price.is_zero(), Error::<T>::InvalidOraclePrice);
+		}
+		Ok(())
+	}
+
+	/// Record `price` in the open round of `currency_id`, replacing any
+	/// earlier submission of `who`, and aggregate the round once it reaches
+	/// `OracleQuorum`. Outliers are dropped from the round, and a round open
+	/// for `RoundTimeout` blocks is discarded for a new one.
+	fn submit_round_price(currency_id: CurrencyIdOf<T>, who: &T::AccountId, price: Price) {
+		let now = <frame_system::Module<T>>::block_number();
+		let mut round = Self::oracle_round(currency_id);
+		if let Some(start) = Self::round_start(currency_id) {
+			if now.saturating_sub(start) >= T::RoundTimeout::get() {
+				RoundSubmissions::<T>::remove(currency_id, round);
+				RoundStarts::<T>::remove(currency_id);
+				Self::deposit_event(Event::RoundTimedOut(currency_id, round));
+				round = round.wrapping_add(1);
+				OracleRounds::<T>::insert(currency_id, round);
+			}
+		}
+		if !RoundStarts::<T>::contains_key(currency_id) {
+			RoundStarts::<T>::insert(currency_id, now);
+		}
+
+		let mut submissions = Self::round_submissions(currency_id, round);
+		match submissions.iter_mut().find(|(operator, _)| operator == who) {
+			Some(submission) => submission.1 = price,
+			None => submissions.push((who.clone(), price)),
+		}
+
+		let quorum = T::OracleQuorum::get().max(1) as usize;
+		if submissions.len() < quorum {
+			RoundSubmissions::<T>::insert(currency_id, round, submissions);
+			return;
+		}
+
+		let median = Self::median(submissions.iter().map(|(_, price)| *price).collect());
+		let (accepted, rejected): (Vec<_>, Vec<_>) = submissions
+			.into_iter()
+			.partition(|(_, price)| !Self::exceeds_deviation(*price, median, T::MaxOracleDeviation::get()));
+		for (operator, price) in rejected {
+			Self::deposit_event(Event::SubmissionRejected(currency_id, operator, price));
+		}
+		if accepted.len() < quorum {
+			RoundSubmissions::<T>::insert(currency_id, round, accepted);
+			return;
+		}
+
+		let count = accepted.len() as u32;
+		let price = Self::median(accepted.into_iter().map(|(_, price)| price).collect());
+		OraclePrices::<T>::insert(currency_id, (price, now));
+		RoundSubmissions::<T>::remove(currency_id, round);
+		RoundStarts::<T>::remove(currency_id);
+		OracleRounds::<T>::insert(currency_id, round.wrapping_add(1));
+		Self::deposit_event(Event::AggregatedPrice(currency_id, round, price, count));
+	}
+
+	/// The median of non-empty `prices`, averaging the middle two of an even
+	/// number of prices.
+	fn median(mut prices: Vec<Price>) -> Price {
+		prices.sort();
+		let middle = prices.len() / 2;
+		if prices.len() % 2 == 0 {
+			FixedU128::from_inner(prices[middle - 1].into_inner() / 2 + prices[middle].into_inner() / 2)
+		} else {
+			prices[middle]
+		}
+	}
+
+	/// Check an unsigned price submission comes from an operator, carries
+	/// their current nonce and was fetched within `UnsignedLongevity` blocks.
+	fn validate_price_payload(
+		payload: &PricePayload<T::Public, T::BlockNumber, CurrencyIdOf<T>>,
+	) -> TransactionValidity {
+		if Self::commit_reveal().is_some() {
+			return InvalidTransaction::Call.into();
+		}
+		let who = payload.public.clone().into_account();
+		if Self::operators().binary_search(&who).is_err() {
+			return InvalidTransaction::BadSigner.into();
+		}
+		// a payload failing on dispatch keeps its nonce, so it must not be
+		// valid in the first place
+		if Self::ensure_valid_prices(&payload.prices).is_err() {
+			return InvalidTransaction::Call.into();
+		}
+
+		let nonce = Self::oracle_nonce(&who);
+		if payload.nonce < nonce {
+			return InvalidTransaction::Stale.into();
+		}
+		if payload.nonce > nonce {
+			return InvalidTransaction::Future.into();
+		}
+
+		let now = <frame_system::Module<T>>::block_number();
+		if payload.block_number > now {
+			return InvalidTransaction::Future.into();
+		}
+		let expires_at = payload.block_number.saturating_add(T::UnsignedLongevity::get());
+		if expires_at < now {
+			return InvalidTransaction::Stale.into();
+		}
+
+		ValidTransaction::with_tag_prefix("SerpOracle")
+			.priority(T::UnsignedPriority::get())
+			.and_provides((who, payload.nonce))
+			.longevity(expires_at.saturating_sub(now).saturated_into::<u64>().saturating_add(1))
+			.propagate(true)
+			.build()
+	}
+
+	/// Fetch the prices of the currencies with an oracle symbol and submit
+	/// them with a local operator key, signed or unsigned depending on
+	/// `UnsignedSubmissions`.
+	fn fetch_and_submit_prices() -> Result<(), &'static str> {
+		if Self::commit_reveal().is_some() {
+			return Err("prices are committed and revealed");
+		}
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		if !signer.can_sign() {
+			return Err("no local oracle key");
+		}
+		let prices = Self::fetch_prices().map_err(|_| "failed to fetch prices")?;
+		if prices.is_empty() {
+			return Ok(());
+		}
+
+		if !T::UnsignedSubmissions::get() {
+			return match signer.send_signed_transaction(|_account| Call::submit_prices(prices.clone())) {
+				Some((_, Ok(()))) => Ok(()),
+				_ => Err("failed to submit prices"),
+			};
+		}
+
+		let block_number = <frame_system::Module<T>>::block_number();
+		let result = signer.send_unsigned_transaction(
+			|account| PricePayload {
+				public: account.public.clone(),
+				block_number,
+				nonce: Self::oracle_nonce(&account.id),
+				prices: prices.clone(),
+			},
+			|payload, signature| Call::submit_prices_unsigned(payload, signature),
+		);
+		match result {
+			Some((_, Ok(()))) => Ok(()),
+			_ => Err("failed to submit prices"),
+		}
+	}
+
+	/// Fetch the price endpoint and parse the prices of the currencies with
+	/// an oracle symbol out of it.
+	fn fetch_prices() -> Result<Vec<(CurrencyIdOf<T>, Price)>, http::Error> {
+		let symbols = OracleSymbols::<T>::iter().collect::<Vec<_>>();
+		if symbols.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT_PERIOD));
+		let pending = http::Request::get(T::PriceEndpoint::get())
+			.deadline(deadline)
+			.send()
+			.map_err(|_| http::Error::IoError)?;
+		let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+		if response.code != 200 {
+			native::warn!("💸 Unexpected status code from the price endpoint: {}", response.code);
+			return Err(http::Error::Unknown);
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+		let body = sp_std::str::from_utf8(&body).map_err(|_| http::Error::Unknown)?;
+		Ok(Self::parse_prices(body, &symbols))
+	}
+
+	/// Parse the prices listed under `symbols` out of a JSON object, skipping
+	/// symbols that are missing or not a non-negative number.
+	fn parse_prices(body: &str, symbols: &[(CurrencyIdOf<T>, Vec<u8>)]) -> Vec<(CurrencyIdOf<T>, Price)> {
+		let fields = match lite_json::parse_json(body) {
+			Ok(JsonValue::Object(fields)) => fields,
+			_ => return Vec::new(),
+		};
+
+		symbols
+			.iter()
+			.filter_map(|(currency_id, symbol)| {
+				let (_, value) = fields
+					.iter()
+					.find(|(key, _)| key.iter().copied().eq(symbol.iter().map(|c| *c as char)))?;
+				match value {
+					JsonValue::Number(number) => Self::parse_price(number).map(|price| (*currency_id, price)),
+					_ => None,
+				}
+			})
+			.collect()
+	}
+
+	fn parse_price(number: &NumberValue) -> Option<Price> {
+		if number.negative || number.exponent != 0 {
+			return None;
+		}
+		let fraction = FixedU128::checked_from_rational(
+			number.fraction as u128,
+			10u128.checked_pow(number.fraction_length)?,
+		)?;
+		FixedU128::checked_from_integer(number.integer as u128)?.checked_add(&fraction)
+	}
+
+	fn ensure_valid_memo(memo: &[u8]) -> DispatchResult {
+		ensure!(memo.len() <= T::MaxMemoLength::get() as usize, Error::<T>::MemoTooLong);
+		Ok(())
+	}
+
+	/// Ensure increasing the issuance of `currency_id` by `amount` keeps it
+	/// within `MaxIssuance`.
+	fn ensure_below_ceiling(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		if let Some(max) = Self::max_issuance(currency_id) {
+			let issuance = <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id);
+			ensure!(
+				issuance.checked_add(&amount).map_or(false, |new_issuance| new_issuance <= max),
+				Error::<T>::SupplyCeilingReached
+			);
+		}
+		Ok(())
+	}
+
+	/// Ensure decreasing the issuance of `currency_id` by `amount` keeps it
+	/// within `MinIssuance`.
+	fn ensure_above_floor(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		if let Some(min) = Self::min_issuance(currency_id) {
+			let issuance = <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id);
+			ensure!(issuance.saturating_sub(amount) >= min, Error::<T>::SupplyFloorReached);
+		}
+		Ok(())
+	}
+
+	/// The part of `amount` that can be slashed from `currency_id` without
+	/// pushing its issuance below `MinIssuance`.
+	fn slashable(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		match Self::min_issuance(currency_id) {
+			Some(min) => {
+				let issuance = <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id);
+				amount.min(issuance.saturating_sub(min))
+			}
+			None => amount,
+		}
+	}
+
+	fn beneficiary_account(beneficiary: Beneficiary<T::AccountId>) -> T::AccountId {
+		match beneficiary {
+			Beneficiary::Treasury => T::TreasuryAccount::get(),
+			Beneficiary::SavingsPool => T::SavingsPoolAccount::get(),
+			Beneficiary::SerperPool => T::SerperPoolAccount::get(),
+			Beneficiary::Account(who) => who,
+		}
+	}
+
+	/// Mint `amount` of `currency_id` to the beneficiaries of its expansion
+	/// distribution. The last beneficiary receives any rounding remainder.
+	fn distribute_expansion(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		distribution: Vec<(Beneficiary<T::AccountId>, Perbill)>,
+	) -> DispatchResult {
+		with_transaction_result(|| {
+			let last = distribution.len().saturating_sub(1);
+			let mut remaining = amount;
+			let mut payouts = Vec::with_capacity(distribution.len());
+			for (index, (beneficiary, share)) in distribution.into_iter().enumerate() {
+				let payout = if index == last {
+					remaining
+				} else {
+					(share * amount).min(remaining)
+				};
+				remaining -= payout;
+
+				if beneficiary == Beneficiary::SerperPool {
+					Self::accumulate_rewards(currency_id, payout);
+				}
+				let who = Self::beneficiary_account(beneficiary);
+				T::Stp258Currency::deposit(currency_id, &who, payout)?;
+				payouts.push((who, payout));
+			}
+
+			Self::deposit_event(Event::ExpansionDistributed(currency_id, payouts));
+			Ok(())
+		})
+	}
+
+	/// Redeem outstanding bonds of `currency_id` for up to `amount`, oldest
+	/// first, dropping any expired bonds met on the way.
+	///
+	/// Returns the amount redeemed.
+	fn redeem_bonds(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> result::Result<BalanceOf<T>, DispatchError> {
+		let now = <frame_system::Module<T>>::block_number();
+		let (mut head, tail) = Self::bond_queue_bounds(currency_id);
+		let mut remaining = amount;
+		let mut processed = 0u32;
+
+		while head < tail && !remaining.is_zero() && processed < T::MaxBondRedemptions::get() {
+			processed += 1;
+			let mut bond = match BondQueue::<T>::get(currency_id, head) {
+				Some(bond) => bond,
+				None => {
+					head += 1;
+					continue;
+				}
+			};
+
+			let redeemed = if bond.expires_at <= now {
+				Self::deposit_event(Event::BondExpired(currency_id, bond.owner.clone(), bond.amount));
+				Zero::zero()
+			} else {
+				let redeemed = bond.amount.min(remaining);
+				T::Stp258Currency::deposit(currency_id, &bond.owner, redeemed)?;
+				Self::deposit_event(Event::BondRedeemed(currency_id, bond.owner.clone(), redeemed));
+				redeemed
+			};
+			remaining -= redeemed;
+
+			let settled = if redeemed.is_zero() { bond.amount } else { redeemed };
+			AccountBonds::<T>::mutate_exists(currency_id, &bond.owner, |bonds| {
+				*bonds = bonds.map(|b| b.saturating_sub(settled)).filter(|b| !b.is_zero());
+			});
+			bond.amount -= settled;
+
+			if bond.amount.is_zero() {
+				BondQueue::<T>::remove(currency_id, head);
+				head += 1;
+			} else {
+				BondQueue::<T>::insert(currency_id, head, bond);
+			}
+		}
+
+		BondQueueBounds::<T>::insert(currency_id, (head, tail));
+		Ok(amount - remaining)
+	}
+
+	/// Open a contraction auction of `currency_id` for `amount`. An auction
+	/// already open is topped up and restarted at the new quote price.
+	fn open_auction(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		quote: &QuotedPrice<CurrencyIdOf<T>>,
+	) -> DispatchResult {
+		let params = Self::auction_params(currency_id).ok_or(Error::<T>::AuctionParamsNotSet)?;
+		let now = <frame_system::Module<T>>::block_number();
+		let (target, remaining) = Self::auction(currency_id)
+			.map(|auction| (auction.target, auction.remaining))
+			.unwrap_or_default();
+		let auction = ContractionAuction {
+			target: target.saturating_add(amount),
+			remaining: remaining.saturating_add(amount),
+			quote_price: Self::quote_per_base_unit(quote),
+			start: now,
+			end: now.saturating_add(params.duration),
+		};
+		Auctions::<T>::insert(currency_id, auction);
+		AuctionExpiries::<T>::append(auction.end.saturating_add(One::one()), currency_id);
+
+		Self::deposit_event(Event::AuctionOpened(currency_id, auction.remaining, auction.end));
+		Ok(())
+	}
+
+	/// The stable currency asked per whole native currency unit by the
+	/// auction of `currency_id` at block `now`.
+	fn auction_price(
+		currency_id: CurrencyIdOf<T>,
+		auction: &ContractionAuction<BalanceOf<T>, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> Option<u128> {
+		let params = Self::auction_params(currency_id)?;
+		let quote_price = auction.quote_price.saturated_into::<u128>();
+		let elapsed = now.saturating_sub(auction.start).saturated_into::<u128>();
+
+		let start_price = quote_price.saturating_add(params.start_premium * quote_price);
+		let floor_price = quote_price.saturating_sub(params.max_discount * quote_price);
+		let decay = (params.decay_per_block * quote_price).saturating_mul(elapsed);
+		Some(start_price.saturating_sub(decay).max(floor_price))
+	}
+
+	/// The most native currency the auction of `currency_id` can pay out for
+	/// `amount`, which is at its maximum discount to `quote`.
+	fn auction_payout(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		quote: &QuotedPrice<CurrencyIdOf<T>>,
+	) -> Option<BalanceOf<T>> {
+		let params = Self::auction_params(currency_id)?;
+		let quote_price = Self::quote_per_base_unit(quote).saturated_into::<u128>();
+		let floor_price = quote_price.saturating_sub(params.max_discount * quote_price);
+		let native_base_unit = Self::base_unit(T::GetStp258NativeId::get()).saturated_into::<u128>();
+		Some((amount.saturated_into::<u128>().saturating_mul(native_base_unit) / floor_price.max(1)).saturated_into())
+	}
+
+	/// Credit `amount` of `currency_id`, paid into `SerperPoolAccount`, to the
+	/// serper pool's stake. With no stake in the pool it stays unclaimed in
+	/// the account.
+	fn accumulate_rewards(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) {
+		let total = Self::serper_pool(currency_id).total;
+		if total.is_zero() || amount.is_zero() {
+			return;
+		}
+
+		let increase = FixedU128::saturating_from_rational(amount.saturated_into::<u128>(), total.saturated_into::<u128>());
+		RewardPerShare::<T>::mutate(currency_id, |index| *index = index.saturating_add(increase));
+	}
+
+	/// Move the rewards `who` earned since its last settlement into
+	/// `PendingRewards`. Must be called before its stake changes.
+	fn settle_rewards(currency_id: CurrencyIdOf<T>, who: &T::AccountId) {
+		let index = Self::reward_per_share(currency_id);
+		let last_index = RewardIndexOf::<T>::get(currency_id, who);
+		if index == last_index {
+			return;
+		}
+
+		let stake = Self::serper_stake(currency_id, who).saturated_into::<u128>();
+		let earned: BalanceOf<T> = index.saturating_sub(last_index).saturating_mul_int(stake).saturated_into();
+		if !earned.is_zero() {
+			PendingRewards::<T>::mutate(currency_id, who, |pending| *pending = pending.saturating_add(earned));
+		}
+		RewardIndexOf::<T>::insert(currency_id, who, index);
+	}
+
+	/// Burn `amount` of `currency_id` pro rata from the reserves of its serper
+	/// pool members, paying each of them native currency at `quote` for what
+	/// was actually burnt. Returns the amount burnt.
+	fn contract_serper_pool(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		quote: &QuotedPrice<CurrencyIdOf<T>>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		let mut pool = Self::serper_pool(currency_id);
+		ensure!(pool.total >= amount, Error::<T>::InsufficientSerperPool);
+
+		let total = pool.total.saturated_into::<u128>();
+		let amount_u128 = amount.saturated_into::<u128>();
+
+		with_transaction_result(|| {
+			// shares are taken from the running total so that they sum to `amount`
+			let mut cumulative_stake = 0u128;
+			let mut cumulative_share = 0u128;
+			let mut contracted: BalanceOf<T> = Zero::zero();
+			for (who, stake) in SerperStakes::<T>::iter_prefix(currency_id).collect::<Vec<_>>() {
+				cumulative_stake = cumulative_stake.saturating_add(stake.saturated_into::<u128>());
+				let share_to = cumulative_stake.saturating_mul(amount_u128) / total;
+				let share: BalanceOf<T> = share_to.saturating_sub(cumulative_share).saturated_into();
+				cumulative_share = share_to;
+				if share.is_zero() {
+					continue;
+				}
+
+				Self::settle_rewards(currency_id, &who);
+				let gap = <Self as Stp258CurrencyReservable<T::AccountId>>::slash_reserved(currency_id, &who, share);
+				let burnt = share - gap;
+				if burnt.is_zero() {
+					continue;
+				}
+				if burnt == stake {
+					SerperStakes::<T>::remove(currency_id, &who);
+					pool.members = pool.members.saturating_sub(1);
+				} else {
+					SerperStakes::<T>::insert(currency_id, &who, stake - burnt);
+				}
+				pool.total = pool.total.saturating_sub(burnt);
+				contracted = contracted.saturating_add(burnt);
+
+				let native_amount = Self::quote_to_base(quote, burnt).ok_or(Error::<T>::InvalidQuotePrice)?;
+				Self::pay_from_reserve(currency_id, &who, native_amount)?;
+
+				Self::deposit_event(Event::SerperContracted(currency_id, who, burnt, native_amount));
+			}
+
+			SerperPools::<T>::insert(currency_id, pool);
+			Ok(contracted)
+		})
+	}
+
+	/// Interpret `amount` as the smallest units of `quote` paid for one whole
+	/// unit of `base`.
+	pub fn quoted_price(
+		base: CurrencyIdOf<T>,
+		quote: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> Option<QuotedPrice<CurrencyIdOf<T>>> {
+		let price = FixedU128::checked_from_rational(amount.saturated_into::<u128>(), Self::base_unit(quote).saturated_into::<u128>())?;
+		Some(QuotedPrice { base, quote, price })
+	}
+
+	/// The price of one whole unit of `currency_id` in the reference unit its
+	/// peg is set in, `amount` being given to the precision of its base unit
+	/// so that `base_unit` is the peg.
+	pub fn reference_price(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> Option<Price> {
+		Price::checked_from_rational(amount.saturated_into::<u128>(), Self::base_unit(currency_id).saturated_into::<u128>())
+	}
+
+	/// The price of `base` in `quote` according to the price source.
+	pub fn reference_quote(base: CurrencyIdOf<T>, quote: CurrencyIdOf<T>) -> Option<QuotedPrice<CurrencyIdOf<T>>> {
+		let (base_price, _) = Self::price(base)?;
+		let (quote_price, _) = Self::price(quote)?;
+		let price = base_price.checked_div(&quote_price)?;
+		Some(QuotedPrice { base, quote, price })
+	}
+
+	/// The price of `currency_id` from the first source of the fallback
+	/// chain that has one: the providers of `PriceSource` in order, then the
+	/// last good price if it is at most `MaxPriceStaleness` blocks old.
+	pub fn price(currency_id: CurrencyIdOf<T>) -> Option<(Price, PriceSourceKind)> {
+		if let Some((price, position)) = T::PriceSource::get_price(currency_id) {
+			return Some((price, PriceSourceKind::Provider(position)));
+		}
+		let (price, updated_at) = Self::last_good_price(currency_id)?;
+		let now = <frame_system::Module<T>>::block_number();
+		if now.saturating_sub(updated_at) > T::MaxPriceStaleness::get() {
+			return None;
+		}
+		Some((price, PriceSourceKind::LastGood))
+	}
+
+	/// Record the price of `currency_id` a SERP round is run at and its
+	/// source, keeping fresh prices as the last good price.
+	fn record_price(currency_id: CurrencyIdOf<T>, price: Price, source: PriceSourceKind) {
+		if source != PriceSourceKind::LastGood {
+			LastGoodPrices::<T>::insert(currency_id, (price, <frame_system::Module<T>>::block_number()));
+		}
+		PriceSources::<T>::insert(currency_id, source);
+		Self::deposit_event(Event::PriceSourced(currency_id, price, source));
+	}
+
+	/// The price `quote` of the native currency is checked against: the
+	/// time-weighted average rate of the quote currency's liquidity pool, or
+	/// the price source's until the pool has a full `TwapPeriod` window.
+	fn quote_reference(
+		quote: &QuotedPrice<CurrencyIdOf<T>>,
+	) -> result::Result<QuotedPrice<CurrencyIdOf<T>>, DispatchError> {
+		let twap = Self::pool_twap_price(quote.quote)
+			.filter(|_| quote.base == T::GetStp258NativeId::get())
+			.and_then(|twap| Price::one().checked_div(&twap));
+		if let Some(price) = twap {
+			return Ok(QuotedPrice { base: quote.base, quote: quote.quote, price });
+		}
+
+		let reference = Self::reference_quote(quote.base, quote.quote).ok_or(Error::<T>::PriceUnavailable)?;
+		Ok(reference)
+	}
+
+	/// Ensure `quote` is within `MaxQuoteDeviation` of its reference price,
+	/// emitting `QuoteRejected` if it is not.
+	fn ensure_quote_within_bounds(quote: &QuotedPrice<CurrencyIdOf<T>>) -> DispatchResult {
+		let reference = Self::quote_reference(quote)?;
+		if Self::exceeds_deviation(quote.price, reference.price, T::MaxQuoteDeviation::get()) {
+			Self::deposit_event(Event::QuoteRejected(
+				quote.quote,
+				Self::quote_per_base_unit(quote),
+				Self::quote_per_base_unit(&reference),
+			));
+			return Err(Error::<T>::QuoteDeviationTooLarge.into());
+		}
+		Ok(())
+	}
 
-pub use module::*;
+	/// Whether `price` is further than `max_deviation` of `reference` away
+	/// from it.
+	fn exceeds_deviation(price: FixedU128, reference: FixedU128, max_deviation: Permill) -> bool {
+		let deviation = if price > reference {
+			price.saturating_sub(reference)
+		} else {
+			reference.saturating_sub(price)
+		};
+		deviation > reference.saturating_mul(max_deviation.into())
+	}
 
-#[frame_support::pallet]
-pub mod module {
-	use super::*;
+	/// The smallest units of `price.quote` paid for one whole unit of
+	/// `price.base`.
+	pub fn quote_per_base_unit(price: &QuotedPrice<CurrencyIdOf<T>>) -> BalanceOf<T> {
+		price
+			.price
+			.saturating_mul_int(Self::base_unit(price.quote).saturated_into::<u128>())
+			.saturated_into()
+	}
 
-	pub trait WeightInfo {
-		fn transfer_non_native_currency() -> Weight;
-		fn transfer_native_currency() -> Weight;
-		fn update_balance_non_native_currency() -> Weight;
-		fn update_balance_native_currency_creating() -> Weight;
-		fn update_balance_native_currency_killing() -> Weight;
+	/// Convert `amount` of `price.base` into `price.quote`, rounding down.
+	pub fn base_to_quote(price: &QuotedPrice<CurrencyIdOf<T>>, amount: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		multiply_by_rational(
+			amount.saturated_into::<u128>(),
+			Self::quote_per_base_unit(price).saturated_into::<u128>(),
+			Self::base_unit(price.base).saturated_into::<u128>(),
+		)
+		.ok()
+		.map(|amount| amount.saturated_into())
 	}
 
-	pub(crate) type BalanceOf<T> =
-		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::Balance;
-	pub(crate) type CurrencyIdOf<T> =
-		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::CurrencyId;
-	pub(crate) type AmountOf<T> =
-		<<T as Config>::Stp258Currency as Stp258CurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
-	pub(crate) type BlockOf<T> =
-		<<T as Config>::Stp258Currency as SerpTes<<T as frame_system::Config>::AccountId>>::BlockNumber;
+	/// Convert `amount` of `price.quote` into `price.base`, rounding down.
+	pub fn quote_to_base(price: &QuotedPrice<CurrencyIdOf<T>>, amount: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		multiply_by_rational(
+			amount.saturated_into::<u128>(),
+			Self::base_unit(price.base).saturated_into::<u128>(),
+			Self::quote_per_base_unit(price).saturated_into::<u128>(),
+		)
+		.ok()
+		.map(|amount| amount.saturated_into())
+	}
 
-	#[pallet::config]
-	pub trait Config: frame_system::Config {
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	/// Contract `amount` of `stable_currency_id` through the inner SERP,
+	/// halving the attempted amount whenever it cannot be filled.
+	///
+	/// Returns the amount contracted, at most `MaxContractionAttempts`
+	/// attempts being made.
+	fn contract_partially(
+		native_currency_id: CurrencyIdOf<T>,
+		stable_currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		quote: &QuotedPrice<CurrencyIdOf<T>>,
+	) -> BalanceOf<T> {
+		let quote_price = Self::quote_per_base_unit(quote);
+		let mut filled = BalanceOf::<T>::zero();
+		let mut attempt = amount;
+		let mut attempts = 0u32;
+		while !attempt.is_zero() && attempts < T::MaxContractionAttempts::get() {
+			attempts += 1;
+			let result = with_transaction_result(|| {
+				T::Stp258Currency::contract_supply(native_currency_id, stable_currency_id, attempt, quote_price)
+			});
+			if result.is_ok() {
+				filled = filled.saturating_add(attempt);
+				attempt = attempt.min(amount.saturating_sub(filled));
+			} else {
+				attempt = attempt / 2u32.into();
+			}
+		}
+		filled
+	}
 
-		type Stp258Currency: MergeAccount<Self::AccountId>
-			+ Stp258CurrencyExtended<Self::AccountId>
-			+ Stp258CurrencyLockable<Self::AccountId>
-			+ Stp258CurrencyReservable<Self::AccountId>
-			+ SerpMarket<Self::AccountId>
-			+ SerpTes<Self::AccountId>;
+	/// Set the contraction debt of `currency_id`, emitting
+	/// `ContractionDebtChanged` if it changed.
+	fn set_contraction_debt(currency_id: CurrencyIdOf<T>, debt: BalanceOf<T>) {
+		if debt != Self::contraction_debt(currency_id) {
+			ContractionDebt::<T>::insert(currency_id, debt);
+			Self::deposit_event(Event::ContractionDebtChanged(currency_id, debt));
+		}
+	}
 
-		type Stp258Native: Stp258AssetExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
-			+ Stp258AssetLockable<Self::AccountId, Balance = BalanceOf<Self>>
-			+ Stp258AssetReservable<Self::AccountId, Balance = BalanceOf<Self>>;
+	/// The total issuance of `currency_id` less the balances of its excluded
+	/// accounts.
+	pub fn circulating_supply(currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
+		Self::excluded_accounts(currency_id).iter().fold(
+			<Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id),
+			|supply, who| supply.saturating_sub(<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, who)),
+		)
+	}
 
-		#[pallet::constant]
-		type GetStp258NativeId: Get<CurrencyIdOf<Self>>;
+	/// The account holding the native currency reserve of `currency_id`.
+	pub fn reserve_account(currency_id: CurrencyIdOf<T>) -> T::AccountId {
+		T::ModuleId::get().into_sub_account(currency_id)
+	}
 
-		/// Weight information for extrinsics in this module.
-		type WeightInfo: WeightInfo;
+	/// The value of the reserve of `currency_id` over the value of its total
+	/// issuance, at the price source's current prices.
+	///
+	/// Returns `None` if a price is unavailable or nothing is issued.
+	pub fn collateral_ratio(currency_id: CurrencyIdOf<T>) -> Option<FixedU128> {
+		Self::collateral_ratio_of(
+			currency_id,
+			Self::reserve(currency_id),
+			<Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id),
+		)
 	}
 
-	#[pallet::error]
-	pub enum Error<T> {
-		/// Unable to convert the Amount type into Balance.
-		AmountIntoBalanceFailed,
-		/// Balance is too low.
-		BalanceTooLow,
+	fn collateral_ratio_of(
+		currency_id: CurrencyIdOf<T>,
+		reserve: BalanceOf<T>,
+		issuance: BalanceOf<T>,
+	) -> Option<FixedU128> {
+		let native_currency_id = T::GetStp258NativeId::get();
+		let (native_price, _) = Self::price(native_currency_id)?;
+		let (stable_price, _) = Self::price(currency_id)?;
+		let native_base_unit = Self::base_unit(native_currency_id).saturated_into::<u128>();
+		let stable_base_unit = Self::base_unit(currency_id).saturated_into::<u128>();
+
+		let reserve_value = native_price
+			.checked_mul(&FixedU128::checked_from_rational(reserve.saturated_into::<u128>(), native_base_unit)?)?;
+		let issuance_value = stable_price
+			.checked_mul(&FixedU128::checked_from_rational(issuance.saturated_into::<u128>(), stable_base_unit)?)?;
+		reserve_value.checked_div(&issuance_value)
 	}
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Currency transfer success. [currency_id, from, to, amount]
-		Transferred(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
-		/// Update balance success. [currency_id, who, amount]
-		BalanceUpdated(CurrencyIdOf<T>, T::AccountId, AmountOf<T>),
-		/// Deposit success. [currency_id, who, amount]
-		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-		/// Withdraw success. [currency_id, who, amount]
-		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-		// Supply Expansion Successful. \[currency_id, expand_by\]
-		SerpedUpSupply(CurrencyIdOf<T>, BalanceOf<T>),
-		/// Supply Contraction Successful. \[currency_id, contract_by\]
-		SerpedDownSupply(CurrencyIdOf<T>, BalanceOf<T>),
-		// Serp Elast Successful. \[currency_id, expand_by\]
-		SerpElast(CurrencyIdOf<T>),
-		/// On Serp Block Successful. \[currency_id, contract_by\]
-		OnSerpBlock(CurrencyIdOf<T>),
+	/// Ensure contracting `currency_id` by `amount`, paying `native_payout`
+	/// out of its reserve, keeps it above `MinCollateralRatio`.
+	fn ensure_collateral_ratio(
+		currency_id: CurrencyIdOf<T>,
+		amount: BalanceOf<T>,
+		native_payout: BalanceOf<T>,
+	) -> DispatchResult {
+		if let Some(min_ratio) = Self::min_collateral_ratio(currency_id) {
+			let issuance = <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id).saturating_sub(amount);
+			if issuance.is_zero() {
+				return Ok(());
+			}
+			let reserve = Self::reserve(currency_id).saturating_sub(native_payout);
+			let ratio = Self::collateral_ratio_of(currency_id, reserve, issuance).ok_or(Error::<T>::PriceUnavailable)?;
+			ensure!(ratio >= min_ratio, Error::<T>::CollateralRatioTooLow);
+		}
+		Ok(())
 	}
 
-	#[pallet::pallet]
-	pub struct Pallet<T>(PhantomData<T>);
+	/// The account holding the native/`currency_id` liquidity pool.
+	pub fn pool_account(currency_id: CurrencyIdOf<T>) -> T::AccountId {
+		T::ModuleId::get().into_sub_account((b"pool", currency_id))
+	}
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	/// The spot price of a whole unit of `currency_id` in native currency
+	/// from its liquidity pool.
+	pub fn pool_spot_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		if currency_id == T::GetStp258NativeId::get() {
+			return Some(Price::one());
+		}
+		let pool = Self::liquidity_pool(currency_id);
+		let native = Price::checked_from_rational(
+			pool.native.saturated_into::<u128>(),
+			Self::base_unit(T::GetStp258NativeId::get()).saturated_into::<u128>(),
+		)?;
+		let stable = Price::checked_from_rational(
+			pool.stable.saturated_into::<u128>(),
+			Self::base_unit(currency_id).saturated_into::<u128>(),
+		)?;
+		if native.is_zero() || stable.is_zero() {
+			return None;
+		}
+		native.checked_div(&stable)
+	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Transfer some balance to another account under `currency_id`.
-		///
-		/// The dispatch origin for this call must be `Signed` by the
-		/// transactor.
-		#[pallet::weight(T::WeightInfo::transfer_non_native_currency())]
-		pub fn transfer(
-			origin: OriginFor<T>,
-			dest: <T::Lookup as StaticLookup>::Source,
-			currency_id: CurrencyIdOf<T>,
-			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResultWithPostInfo {
-			let from = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(dest)?;
-			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
-			Ok(().into())
+	/// The time-weighted average price of a whole unit of `currency_id` in
+	/// native currency from its liquidity pool, over the current window once
+	/// it spans `TwapPeriod` blocks and over the last closed one until then.
+	pub fn pool_twap_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		if currency_id == T::GetStp258NativeId::get() {
+			return Some(Price::one());
 		}
+		let now = <frame_system::Module<T>>::block_number();
+		let (start, latest) = Self::pool_observations(currency_id)?;
+		if now.saturating_sub(start.at) >= T::TwapPeriod::get() {
+			let current = Self::accrue_observation(currency_id, latest, now)?;
+			return Self::average_price(&start, &current);
+		}
+		PoolTwaps::<T>::get(currency_id)
+	}
 
-		/// Transfer some native currency to another account.
-		///
-		/// The dispatch origin for this call must be `Signed` by the
-		/// transactor.
-		#[pallet::weight(T::WeightInfo::transfer_native_currency())]
-		pub fn transfer_native_currency(
-			origin: OriginFor<T>,
-			dest: <T::Lookup as StaticLookup>::Source,
-			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResultWithPostInfo {
-			let from = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(dest)?;
-			T::Stp258Native::transfer(&from, &to, amount)?;
+	/// `observation` carried forward to `now` at the pool's spot price.
+	fn accrue_observation(
+		currency_id: CurrencyIdOf<T>,
+		observation: PriceObservation<T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> Option<PriceObservation<T::BlockNumber>> {
+		let spot = Self::pool_spot_price(currency_id)?;
+		let elapsed = Price::saturating_from_integer(now.saturating_sub(observation.at).saturated_into::<u128>());
+		Some(PriceObservation {
+			price_cumulative: observation.price_cumulative.saturating_add(spot.saturating_mul(elapsed)),
+			at: now,
+		})
+	}
 
-			Self::deposit_event(Event::Transferred(T::GetStp258NativeId::get(), from, to, amount));
-			Ok(().into())
+	fn average_price(
+		start: &PriceObservation<T::BlockNumber>,
+		end: &PriceObservation<T::BlockNumber>,
+	) -> Option<Price> {
+		let blocks = end.at.saturating_sub(start.at).saturated_into::<u128>();
+		if blocks.is_zero() {
+			return None;
 		}
+		end.price_cumulative
+			.saturating_sub(start.price_cumulative)
+			.checked_div(&Price::saturating_from_integer(blocks))
+	}
 
-		/// update amount of account `who` under `currency_id`.
-		///
-		/// The dispatch origin of this call must be _Root_.
-		#[pallet::weight(T::WeightInfo::update_balance_non_native_currency())]
-		pub fn update_balance(
-			origin: OriginFor<T>,
-			who: <T::Lookup as StaticLookup>::Source,
-			currency_id: CurrencyIdOf<T>,
-			amount: AmountOf<T>,
-		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			let dest = T::Lookup::lookup(who)?;
-			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
-			Ok(().into())
+	/// Accrue the pool's spot price up to now, before its reserves change,
+	/// closing the TWAP window once it spans `TwapPeriod` blocks.
+	fn update_pool_observations(currency_id: CurrencyIdOf<T>) {
+		let now = <frame_system::Module<T>>::block_number();
+		let observations = Self::pool_observations(currency_id)
+			.and_then(|(start, latest)| Some((start, Self::accrue_observation(currency_id, latest, now)?)));
+		match observations {
+			Some((start, latest)) if now.saturating_sub(start.at) >= T::TwapPeriod::get() => {
+				if let Some(twap) = Self::average_price(&start, &latest) {
+					PoolTwaps::<T>::insert(currency_id, twap);
+				}
+				PoolObservations::<T>::insert(currency_id, (latest, latest));
+			}
+			Some((start, latest)) => PoolObservations::<T>::insert(currency_id, (start, latest)),
+			// an empty pool starts over
+			None => {
+				let observation = PriceObservation { price_cumulative: Zero::zero(), at: now };
+				PoolObservations::<T>::insert(currency_id, (observation, observation));
+				PoolTwaps::<T>::remove(currency_id);
+			}
+		}
+	}
+
+	/// The prices of `currency_id` and of the native currency for its
+	/// mint/redeem at peg window, which is shut while the SERP of
+	/// `currency_id` is paused or its reserve ratio is below its minimum. A
+	/// currency without a minimum collateral ratio has no reserve check.
+	fn peg_window_prices(currency_id: CurrencyIdOf<T>) -> result::Result<(Price, Price), DispatchError> {
+		Self::ensure_serp_active(currency_id)?;
+		ensure!(Self::peg_window_limit(currency_id).is_some(), Error::<T>::PegWindowClosed);
+		if let Some(min_ratio) = Self::min_collateral_ratio(currency_id) {
+			let ratio = Self::collateral_ratio(currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+			ensure!(ratio >= min_ratio, Error::<T>::ReserveRatioUnhealthy);
+		}
+		let (stable_price, _) = Self::price(currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+		let (native_price, _) = Self::price(T::GetStp258NativeId::get()).ok_or(Error::<T>::PriceUnavailable)?;
+		ensure!(!native_price.is_zero(), Error::<T>::InvalidQuotePrice);
+		Ok((stable_price, native_price))
+	}
+
+	/// The window usage of `currency_id` after `amount` more, if within its
+	/// limit.
+	fn use_peg_window(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> result::Result<BalanceOf<T>, DispatchError> {
+		let limit = Self::peg_window_limit(currency_id).ok_or(Error::<T>::PegWindowClosed)?;
+		let used = Self::peg_window_used(currency_id).saturating_add(amount);
+		ensure!(used <= limit, Error::<T>::PegWindowLimitExceeded);
+		Ok(used)
+	}
+
+	/// The account holding the collateral of the peg stability module. The
+	/// fees it keeps stay there as surplus.
+	pub fn psm_account() -> T::AccountId {
+		T::ModuleId::get().into_sub_account(b"psm")
+	}
+
+	/// `amount` of `from` in smallest units of `to`, one whole unit of each
+	/// being worth the same.
+	fn at_peg(from: CurrencyIdOf<T>, to: CurrencyIdOf<T>, amount: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		let amount = multiply_by_rational(
+			amount.saturated_into(),
+			Self::base_unit(to).saturated_into(),
+			Self::base_unit(from).saturated_into(),
+		)
+		.ok()?;
+		amount.try_into().ok()
+	}
+
+	/// `a * b / c`, rounded up.
+	fn mul_div_ceil(a: u128, b: u128, c: u128) -> Option<u128> {
+		if c.is_zero() {
+			return None;
+		}
+		let c = U256::from(c);
+		let result = (U256::from(a) * U256::from(b) + c - U256::one()) / c;
+		if result > U256::from(u128::max_value()) {
+			return None;
+		}
+		Some(result.low_u128())
+	}
+
+	/// Pay `amount` of native currency to `who` out of the reserve of
+	/// `currency_id`, failing if the reserve cannot cover it.
+	fn pay_from_reserve(currency_id: CurrencyIdOf<T>, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		ensure!(Self::reserve(currency_id) >= amount, Error::<T>::InsufficientReserve);
+		T::Stp258Native::transfer(&Self::reserve_account(currency_id), who, amount)?;
+		Reserves::<T>::mutate(currency_id, |reserve| *reserve -= amount);
+		Ok(())
+	}
+
+	/// The change a SERP round at `price`, see `reference_price`, makes to
+	/// the circulating supply of `currency_id`, along with the fraction of a
+	/// smallest unit truncated from the new supply. The fraction left over by
+	/// the last applied round is added in. `None` if the new supply cannot be
+	/// represented.
+	pub fn supply_adjustment(
+		currency_id: CurrencyIdOf<T>,
+		price: Price,
+	) -> Option<(SupplyAdjustment<BalanceOf<T>>, FixedU128)> {
+		let price = price.into_inner();
+		let accuracy = FixedU128::accuracy();
+		let supply = Self::circulating_supply(currency_id).saturated_into::<u128>();
+
+		// `supply * price` split into whole and fractional smallest units
+		let whole = multiply_by_rational(supply, price, accuracy).ok()?;
+		let fraction = (supply % accuracy) * (price % accuracy) % accuracy
+			+ Self::supply_change_remainder(currency_id).into_inner();
+		let target = whole.checked_add(fraction / accuracy)?;
+		let adjustment = if target >= supply {
+			SupplyAdjustment::Expand((target - supply).saturated_into())
+		} else {
+			SupplyAdjustment::Contract((supply - target).saturated_into())
+		};
+		Some((adjustment, FixedU128::from_inner(fraction % accuracy)))
+	}
+
+	/// Apply the change a SERP round at `price`, see `reference_price`, makes
+	/// to the supply of `stable_currency_id`, paying or charging the native
+	/// currency at `quote_price`, see `quoted_price`.
+	fn adjust_supply(
+		native_currency_id: CurrencyIdOf<T>,
+		stable_currency_id: CurrencyIdOf<T>,
+		price: Price,
+		quote_price: BalanceOf<T>,
+	) -> DispatchResult {
+		let (adjustment, remainder) =
+			Self::supply_adjustment(stable_currency_id, price).ok_or(Error::<T>::InvalidQuotePrice)?;
+		match adjustment {
+			SupplyAdjustment::Expand(amount) => <Self as SerpMarket<T::AccountId>>::expand_supply(
+				native_currency_id,
+				stable_currency_id,
+				amount,
+				quote_price,
+			)?,
+			SupplyAdjustment::Contract(amount) => <Self as SerpMarket<T::AccountId>>::contract_supply(
+				native_currency_id,
+				stable_currency_id,
+				amount,
+				quote_price,
+			)?,
+		}
+		// the truncated fraction carries over only once the change is applied
+		SupplyChangeRemainder::<T>::insert(stable_currency_id, remainder);
+		Ok(())
+	}
+
+	/// Convert a fee in the native currency into `currency_id` at the price
+	/// source's current prices, adding `FeeMarkup`.
+	///
+	/// Returns `None` if either price is unavailable.
+	pub fn stable_fee(currency_id: CurrencyIdOf<T>, native_fee: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		let native_currency_id = T::GetStp258NativeId::get();
+		let (native_price, _) = Self::price(native_currency_id)?;
+		let (stable_price, _) = Self::price(currency_id)?;
+		let native_base_unit = Self::base_unit(native_currency_id).saturated_into::<u128>();
+		let stable_base_unit = Self::base_unit(currency_id).saturated_into::<u128>();
+		if native_base_unit.is_zero() {
+			return None;
 		}
+
+		let rate = native_price.checked_div(&stable_price)?;
+		let fee = rate
+			.checked_mul_int(native_fee.saturated_into::<u128>().checked_mul(stable_base_unit)?)?
+			/ native_base_unit;
+		let fee = fee.saturating_add(T::FeeMarkup::get().mul_ceil(fee));
+		Some(fee.saturated_into())
 	}
 }
 
@@ -172,22 +3639,29 @@ impl<T: Config> SerpTes<T::AccountId> for Pallet<T> {
 	type BlockNumber = BlockOf<T>;
 	/// Contracts or expands the currency supply based on conditions.
 	/// Filters through the conditions to see whether it's time to adjust supply or not.
+	///
+	/// The round is run at the price of the stable currency from the price
+	/// source, the price given being ignored.
 	fn on_serp_block(
-		now: Self::BlockNumber,
+		_now: Self::BlockNumber,
 		stable_currency_id: Self::CurrencyId,
-		stable_currency_price: Self::Balance, 
+		_stable_currency_price: Self::Balance, 
 		native_currency_id: Self::CurrencyId,
 		native_currency_price: Self::Balance, 
 	) -> DispatchResult {
         if native_currency_id == T::GetStp258NativeId::get() {
 			if stable_currency_id != T::GetStp258NativeId::get() {
-				T::Stp258Currency::on_serp_block(
-					now,
-                    stable_currency_id,
-                    stable_currency_price,
-					native_currency_id, 
-					native_currency_price,
-				)?;
+				let (price, source) = Self::price(stable_currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+				let (native_price, native_source) =
+					Self::price(native_currency_id).ok_or(Error::<T>::PriceUnavailable)?;
+				Self::record_price(stable_currency_id, price, source);
+				Self::record_price(native_currency_id, native_price, native_source);
+				if !Self::accept_serp_price(stable_currency_id, price) {
+					return Ok(());
+				}
+				// every accepted round opens a fresh mint/redeem at peg window
+				PegWindowUsage::<T>::remove(stable_currency_id);
+				Self::adjust_supply(native_currency_id, stable_currency_id, price, native_currency_price)?;
 			} else {
 				native::info!("💸 This currency ({:?}) cannot be serped on block.", stable_currency_id);
 			}
@@ -198,13 +3672,19 @@ impl<T: Config> SerpTes<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
-    /// Calculate the amount of supply change from a fraction.
+    /// Calculate the amount of supply change from a fraction of the
+	/// circulating supply.
+	///
+	/// This is the size of the change either way, `supply_adjustment` giving
+	/// its direction. The fraction of a smallest unit left over by the last
+	/// applied change is added in.
 	fn supply_change(currency_id:  Self::CurrencyId, new_price: Self::Balance) -> Self::Balance {
-		let base_unit = T::Stp258Currency::base_unit(currency_id);
-		let supply = <Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id);
-		let fraction = new_price * supply;
-		let fractioned = fraction / base_unit;
-		fractioned - supply
+		let adjustment = Self::reference_price(currency_id, new_price)
+			.and_then(|price| Self::supply_adjustment(currency_id, price));
+		match adjustment {
+			Some((SupplyAdjustment::Expand(amount), _)) | Some((SupplyAdjustment::Contract(amount), _)) => amount,
+			None => Zero::zero(),
+		}
 	}
 
     /// Expands (if the price is above pegbase) or contracts (if the price is below pegbase) 
@@ -225,12 +3705,10 @@ impl<T: Config> SerpTes<T::AccountId> for Pallet<T> {
 	) -> DispatchResult {
         if native_currency_id == T::GetStp258NativeId::get() {
 			if stable_currency_id != T::GetStp258NativeId::get() {
-				T::Stp258Currency::serp_elast(
-                    stable_currency_id,
-                    stable_currency_price,
-					native_currency_id, 
-					native_currency_price,
-				)?;
+				Self::ensure_serp_active(stable_currency_id)?;
+				let price = Self::reference_price(stable_currency_id, stable_currency_price)
+					.ok_or(Error::<T>::InvalidQuotePrice)?;
+				Self::adjust_supply(native_currency_id, stable_currency_id, price, native_currency_price)?;
 			} else {
 				native::info!("💸 Cannot serp serp native currency ({:?}).", native_currency_id);
 			}
@@ -241,6 +3719,69 @@ impl<T: Config> SerpTes<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 }
+impl<T: Config> ChangeMembers<T::AccountId> for Pallet<T> {
+	fn change_members_sorted(_incoming: &[T::AccountId], outgoing: &[T::AccountId], new: &[T::AccountId]) {
+		for who in outgoing {
+			Self::purge_submissions(who);
+		}
+		let mut operators = new.to_vec();
+		let max_operators = T::MaxOperators::get() as usize;
+		if operators.len() > max_operators {
+			let dropped = operators.split_off(max_operators);
+			native::warn!("💸 {} members exceed MaxOperators and are not oracle operators.", dropped.len());
+			for who in dropped.iter() {
+				Self::purge_submissions(who);
+			}
+			Self::deposit_event(Event::OperatorsTruncated(dropped));
+		}
+		Operators::<T>::put(operators);
+	}
+}
+
+impl<T: Config> InitializeMembers<T::AccountId> for Pallet<T> {
+	fn initialize_members(members: &[T::AccountId]) {
+		if !members.is_empty() {
+			assert!(Self::operators().is_empty(), "Oracle operators are already initialized!");
+			assert!(
+				members.len() <= T::MaxOperators::get() as usize,
+				"Too many initial oracle operators"
+			);
+			let mut operators = members.to_vec();
+			operators.sort();
+			Operators::<T>::put(operators);
+		}
+	}
+}
+
+impl<T: Config> PriceProvider<CurrencyIdOf<T>> for Pallet<T> {
+	fn get_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		Self::oracle_price(currency_id).map(|(price, _)| price)
+	}
+}
+
+/// Prices from the spot rates of the liquidity pools, converted from native
+/// currency to the reference unit at the native price given by `N`.
+pub struct PoolSpotPrice<T, N>(marker::PhantomData<(T, N)>);
+
+impl<T: Config, N: PriceProvider<CurrencyIdOf<T>>> PriceProvider<CurrencyIdOf<T>> for PoolSpotPrice<T, N> {
+	fn get_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		let native_price = N::get_price(T::GetStp258NativeId::get())?;
+		Pallet::<T>::pool_spot_price(currency_id)?.checked_mul(&native_price)
+	}
+}
+
+/// Prices from the time-weighted average rates of the liquidity pools,
+/// converted from native currency to the reference unit at the native price
+/// given by `N`.
+pub struct PoolTwapPrice<T, N>(marker::PhantomData<(T, N)>);
+
+impl<T: Config, N: PriceProvider<CurrencyIdOf<T>>> PriceProvider<CurrencyIdOf<T>> for PoolTwapPrice<T, N> {
+	fn get_price(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		let native_price = N::get_price(T::GetStp258NativeId::get())?;
+		Pallet::<T>::pool_twap_price(currency_id)?.checked_mul(&native_price)
+	}
+}
+
 impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
 	/// Called when `expand_supply` is received from the SERP by the SerpTes 
 	/// through the `on_expand_supply` trigger.
@@ -250,6 +3791,8 @@ impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
 	/// the `native_currency` used to expand settcurrency supply.
 	/// `who` is the account to serp with.
 	/// `quote_price` here is sampled from mock and can be connected to an oracle.
+	/// It is given in the smallest units of the settcurrency per whole unit of
+	/// the native currency, see `quoted_price`.
 	fn expand_supply(
 		native_currency_id: Self::CurrencyId, 
 		stable_currency_id: Self::CurrencyId, 
@@ -259,21 +3802,59 @@ impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
 		if expand_by.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_serp_active(stable_currency_id)?;
+		let mut expanded = expand_by;
 		if native_currency_id == T::GetStp258NativeId::get() {
 			if stable_currency_id != T::GetStp258NativeId::get() {
-				T::Stp258Currency::expand_supply(
-					native_currency_id, 
-					stable_currency_id, 
-					expand_by as Self::Balance, 
-					quote_price,
-				)?;
+				let quote = Self::quoted_price(native_currency_id, stable_currency_id, quote_price)
+					.filter(|quote| !quote.price.is_zero())
+					.ok_or(Error::<T>::InvalidQuotePrice)?;
+				Self::ensure_quote_within_bounds(&quote)?;
+				// contraction still owed by earlier rounds, in debt or in an open
+				// auction, is netted against the expansion first
+				let auctioned = Self::auction(stable_currency_id).map(|auction| auction.remaining).unwrap_or_default();
+				let owed = Self::contraction_debt(stable_currency_id).saturating_add(auctioned);
+				expanded = expand_by.saturating_sub(owed);
+				Self::ensure_below_ceiling(stable_currency_id, expanded)?;
+				with_transaction_result(|| {
+					// bonds are no longer sold once the supply expands again
+					BondWindows::<T>::remove(stable_currency_id);
+					if let Some(auction) = Auctions::<T>::take(stable_currency_id) {
+						Self::deposit_event(Event::AuctionClosed(stable_currency_id, auction.target, auction.remaining));
+					}
+					Self::set_contraction_debt(stable_currency_id, owed.saturating_sub(expand_by));
+					if expanded.is_zero() {
+						return Ok(());
+					}
+
+					// bond holders are paid before anyone else
+					let redeemed = Self::redeem_bonds(stable_currency_id, expanded)?;
+					let remaining = expanded - redeemed;
+					if remaining.is_zero() {
+						return Ok(());
+					}
+
+					let distribution = Self::expansion_distribution(stable_currency_id);
+					if distribution.is_empty() {
+						T::Stp258Currency::expand_supply(
+							native_currency_id, 
+							stable_currency_id, 
+							remaining, 
+							Self::quote_per_base_unit(&quote),
+						)
+					} else {
+						Self::distribute_expansion(stable_currency_id, remaining, distribution)
+					}
+				})?;
 			} else {
 				native::info!("💸 This currency cannot be serped.");
 			}
 		} else {
 			native::info!("💸 The native serping currency is not recognised.");
 		}
-		Self::deposit_event(Event::SerpedUpSupply(stable_currency_id, expand_by));
+		if !expanded.is_zero() {
+			Self::deposit_event(Event::SerpedUpSupply(stable_currency_id, expanded));
+		}
 		Ok(())
 	}
 
@@ -285,6 +3866,8 @@ impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
 	/// the `native_currency` used to contract settcurrency supply.
 	/// `who` is the account to serp with.
 	/// `quote_price` here is sampled from mock and can be connected to an oracle.
+	/// It is given in the smallest units of the settcurrency per whole unit of
+	/// the native currency, see `quoted_price`.
 	fn contract_supply(
 		native_currency_id: Self::CurrencyId, 
 		stable_currency_id: Self::CurrencyId, 
@@ -294,21 +3877,83 @@ impl<T: Config> SerpMarket<T::AccountId> for Pallet<T> {
 		if contract_by.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_serp_active(stable_currency_id)?;
 		if native_currency_id == T::GetStp258NativeId::get() {
 			if stable_currency_id != T::GetStp258NativeId::get() {
-				T::Stp258Currency::contract_supply(
-					native_currency_id, 
-					stable_currency_id, 
-					contract_by,
-					quote_price,
-				)?;
+				let quote = Self::quoted_price(native_currency_id, stable_currency_id, quote_price)
+					.filter(|quote| !quote.price.is_zero())
+					.ok_or(Error::<T>::InvalidQuotePrice)?;
+				Self::ensure_quote_within_bounds(&quote)?;
+				let method = Self::contraction_method(stable_currency_id);
+				// outstanding debt is contracted along with this round's deviation,
+				// and an open auction pays out for what remains of it as well
+				let carried = match method {
+					ContractionMethod::SerperPool => Zero::zero(),
+					_ => ContractionDebt::<T>::get(stable_currency_id),
+				};
+				let (outstanding, native_payout) = match method {
+					ContractionMethod::Auction => {
+						let remaining = Self::auction(stable_currency_id).map(|auction| auction.remaining).unwrap_or_default();
+						let outstanding = remaining.saturating_add(carried).saturating_add(contract_by);
+						let payout = Self::auction_payout(stable_currency_id, outstanding, &quote)
+							.ok_or(Error::<T>::AuctionParamsNotSet)?;
+						(outstanding, payout)
+					}
+					ContractionMethod::SerperPool => {
+						(contract_by, Self::quote_to_base(&quote, contract_by).ok_or(Error::<T>::InvalidQuotePrice)?)
+					}
+					ContractionMethod::Stp258 | ContractionMethod::Bonds => {
+						(carried.saturating_add(contract_by), Zero::zero())
+					}
+				};
+				Self::ensure_above_floor(stable_currency_id, outstanding)?;
+				Self::ensure_collateral_ratio(stable_currency_id, outstanding, native_payout)?;
+
+				// bonds and auctions only contract the supply once they are bought
+				// or bid on
+				let contracted = match method {
+					ContractionMethod::Stp258 => {
+						// outstanding debt is retried ahead of this round's deviation
+						let requested = carried.saturating_add(contract_by);
+						let filled =
+							Self::contract_partially(native_currency_id, stable_currency_id, requested, &quote);
+						if filled < requested {
+							Self::deposit_event(Event::ContractionPartiallyFilled(
+								stable_currency_id,
+								requested,
+								filled,
+							));
+						}
+						Self::set_contraction_debt(stable_currency_id, requested - filled);
+						Some(filled)
+					}
+					ContractionMethod::Bonds => {
+						let capacity = contract_by.saturating_add(carried);
+						BondWindows::<T>::mutate(stable_currency_id, |window| {
+							window.capacity = window.capacity.saturating_add(capacity)
+						});
+						Self::set_contraction_debt(stable_currency_id, Zero::zero());
+						Self::deposit_event(Event::BondCapacityOpened(stable_currency_id, capacity));
+						None
+					}
+					ContractionMethod::Auction => {
+						Self::open_auction(stable_currency_id, contract_by.saturating_add(carried), &quote)?;
+						Self::set_contraction_debt(stable_currency_id, Zero::zero());
+						None
+					}
+					ContractionMethod::SerperPool => {
+						Some(Self::contract_serper_pool(stable_currency_id, contract_by, &quote)?)
+					}
+				};
+				if let Some(contracted) = contracted.filter(|contracted| !contracted.is_zero()) {
+					Self::deposit_event(Event::SerpedDownSupply(stable_currency_id, contracted));
+				}
 			} else {
 				native::info!("💸 This currency cannot be serped.");
 			}
 		} else {
 			native::info!("💸 The native serping currency is not recognised.");
 		}
-		Self::deposit_event(Event::SerpedDownSupply(stable_currency_id, contract_by));
 		Ok(())
 	}
 }
@@ -387,6 +4032,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_below_ceiling(currency_id, amount)?;
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::deposit(who, amount)?;
 		} else {
@@ -400,6 +4046,7 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
+		Self::ensure_above_floor(currency_id, amount)?;
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::withdraw(who, amount)?;
 		} else {
@@ -418,10 +4065,12 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		let slashable = Self::slashable(currency_id, amount);
+		let gap = amount - slashable;
 		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::slash(who, amount)
+			T::Stp258Native::slash(who, slashable) + gap
 		} else {
-			T::Stp258Currency::slash(currency_id, who, amount)
+			T::Stp258Currency::slash(currency_id, who, slashable) + gap
 		}
 	}
 }
@@ -430,6 +4079,15 @@ impl<T: Config> Stp258CurrencyExtended<T::AccountId> for Pallet<T> {
 	type Amount = AmountOf<T>;
 
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
+		let by_balance = by_amount
+			.abs()
+			.try_into()
+			.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
+		if by_amount.is_positive() {
+			Self::ensure_below_ceiling(currency_id, by_balance)?;
+		} else {
+			Self::ensure_above_floor(currency_id, by_balance)?;
+		}
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::update_balance(who, by_amount)?;
 		} else {
@@ -488,10 +4146,12 @@ impl<T: Config> Stp258CurrencyReservable<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		let slashable = Self::slashable(currency_id, value);
+		let gap = value - slashable;
 		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::slash_reserved(who, value)
+			T::Stp258Native::slash_reserved(who, slashable) + gap
 		} else {
-			T::Stp258Currency::slash_reserved(currency_id, who, value)
+			T::Stp258Currency::slash_reserved(currency_id, who, slashable) + gap
 		}
 	}
 
@@ -659,6 +4319,120 @@ where
 
 pub type Stp258NativeOf<T> = Currency<T, <T as Config>::GetStp258NativeId>;
 
+/// Handler for the fees and tips `StableCurrencyFeeAdapter` keeps, the
+/// multi-currency counterpart of `OnUnbalanced`. The amounts have already
+/// been withdrawn from the payer, so the `()` handler burns them.
+pub trait OnFeeUnbalanced<CurrencyId, Balance> {
+	/// Handle the `fee` and `tip` kept in `currency_id`.
+	fn on_unbalanceds(currency_id: CurrencyId, fee: Balance, tip: Balance);
+}
+
+impl<CurrencyId, Balance> OnFeeUnbalanced<CurrencyId, Balance> for () {
+	fn on_unbalanceds(_currency_id: CurrencyId, _fee: Balance, _tip: Balance) {}
+}
+
+/// Deposit transaction fees and tips into the account `A`.
+pub struct DepositFeesTo<T, A>(marker::PhantomData<(T, A)>);
+
+impl<T: Config, A: Get<T::AccountId>> OnFeeUnbalanced<CurrencyIdOf<T>, BalanceOf<T>> for DepositFeesTo<T, A> {
+	fn on_unbalanceds(currency_id: CurrencyIdOf<T>, fee: BalanceOf<T>, tip: BalanceOf<T>) {
+		let amount = fee.saturating_add(tip);
+		let result = if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::deposit(&A::get(), amount)
+		} else {
+			T::Stp258Currency::deposit(currency_id, &A::get(), amount)
+		};
+		if result.is_err() {
+			native::warn!("💸 Could not deposit {:?} of fees in {:?}.", amount, currency_id);
+		}
+	}
+}
+
+/// Charge transaction fees in the stable currency an account selected with
+/// `set_fee_currency`, or in the native currency if none is selected.
+///
+/// Stable fees are converted from the native fee at the `PriceSource` price
+/// plus `FeeMarkup`, and unused weight is refunded in the same currency. What
+/// is kept is handed to `OU`, split into fee and tip.
+pub struct StableCurrencyFeeAdapter<T, OU>(marker::PhantomData<(T, OU)>);
+
+impl<T, OU> OnChargeTransaction<T> for StableCurrencyFeeAdapter<T, OU>
+where
+	T: Config + pallet_transaction_payment::Config,
+	OU: OnFeeUnbalanced<CurrencyIdOf<T>, BalanceOf<T>>,
+{
+	type Balance = BalanceOf<T>;
+	/// The currency charged, the native fee it covered and the amount
+	/// withdrawn in that currency.
+	type LiquidityInfo = Option<(CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>)>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &T::Call,
+		_dispatch_info: &DispatchInfoOf<T::Call>,
+		fee: Self::Balance,
+		_tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let native_currency_id = T::GetStp258NativeId::get();
+		let currency_id = <Pallet<T>>::fee_currency(who).unwrap_or(native_currency_id);
+		let amount = if currency_id == native_currency_id {
+			<Pallet<T>>::ensure_above_floor(currency_id, fee).map_err(|_| InvalidTransaction::Payment)?;
+			T::Stp258Native::withdraw(who, fee).map_err(|_| InvalidTransaction::Payment)?;
+			fee
+		} else {
+			let amount = <Pallet<T>>::stable_fee(currency_id, fee).ok_or(InvalidTransaction::Payment)?;
+			<Pallet<T>>::ensure_above_floor(currency_id, amount).map_err(|_| InvalidTransaction::Payment)?;
+			T::Stp258Currency::withdraw(currency_id, who, amount).map_err(|_| InvalidTransaction::Payment)?;
+			amount
+		};
+
+		Ok(Some((currency_id, fee, amount)))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<T::Call>,
+		_post_info: &PostDispatchInfoOf<T::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		if let Some((currency_id, paid, withdrawn)) = already_withdrawn {
+			// the same share of what was withdrawn as `amount` is of the native fee
+			let share_of_withdrawn = |amount: BalanceOf<T>| -> BalanceOf<T> {
+				FixedU128::checked_from_rational(amount.saturated_into::<u128>(), paid.saturated_into::<u128>())
+					.map(|ratio| ratio.saturating_mul_int(withdrawn.saturated_into::<u128>()))
+					.unwrap_or_default()
+					.saturated_into::<BalanceOf<T>>()
+					.min(withdrawn)
+			};
+			let refund = paid.saturating_sub(corrected_fee);
+			let (refund, tip) = if currency_id == T::GetStp258NativeId::get() {
+				(refund, tip)
+			} else {
+				(share_of_withdrawn(refund), share_of_withdrawn(tip))
+			};
+
+			if !refund.is_zero() {
+				if currency_id == T::GetStp258NativeId::get() {
+					T::Stp258Native::deposit(who, refund).map_err(|_| InvalidTransaction::Payment)?;
+				} else {
+					T::Stp258Currency::deposit(currency_id, who, refund).map_err(|_| InvalidTransaction::Payment)?;
+				}
+			}
+
+			let kept = withdrawn.saturating_sub(refund);
+			let tip = tip.min(kept);
+			OU::on_unbalanceds(currency_id, kept - tip, tip);
+		}
+		Ok(())
+	}
+}
+
 /// Adapt other currency traits implementation to `Stp258Asset`.
 pub struct Stp258AssetAdapter<T, Currency, Amount, Moment>(marker::PhantomData<(T, Currency, Amount, Moment)>);
 
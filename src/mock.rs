@@ -3,14 +3,16 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{construct_runtime, parameter_types, weights::IdentityFee};
+use frame_system::EnsureRoot;
 use serp_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{AccountIdConversion, IdentityLookup},
-	AccountId32, ModuleId, Perbill,
+	testing::{Header, TestXt},
+	traits::{AccountIdConversion, Extrinsic as ExtrinsicT, IdentityLookup, One, Verify},
+	AccountId32, ModuleId, MultiSignature, Perbill,
 };
+use std::{cell::RefCell, collections::BTreeMap};
 
 use crate as serp;
 
@@ -44,8 +46,8 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = ();
 }
 
-type CurrencyId = u32;
-type Balance = u64;
+pub type CurrencyId = u32;
+pub type Balance = u64;
 type Blocknumber = u64;
 
 parameter_types! {
@@ -126,8 +128,120 @@ pub const JUSD: CurrencyId = 3;
 
 pub const ADJUSTMENT_FREQUENCY: Blocknumber = 10;
 
+thread_local! {
+	static PRICES: RefCell<BTreeMap<CurrencyId, Price>> = RefCell::new(BTreeMap::new());
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_price(currency_id: CurrencyId, price: Option<Price>) {
+		PRICES.with(|prices| match price {
+			Some(price) => prices.borrow_mut().insert(currency_id, price),
+			None => prices.borrow_mut().remove(&currency_id),
+		});
+	}
+
+	fn reset() {
+		Self::set_price(DNAR, Some(Price::saturating_from_integer(4)));
+		Self::set_price(SETT, Some(Price::one()));
+		Self::set_price(JUSD, Some(Price::one()));
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		PRICES.with(|prices| prices.borrow().get(&currency_id).copied())
+	}
+}
+
+thread_local! {
+	static UNSIGNED_SUBMISSIONS: RefCell<bool> = RefCell::new(true);
+}
+
+pub struct UnsignedSubmissions;
+impl UnsignedSubmissions {
+	pub fn set(unsigned: bool) {
+		UNSIGNED_SUBMISSIONS.with(|v| *v.borrow_mut() = unsigned);
+	}
+}
+impl Get<bool> for UnsignedSubmissions {
+	fn get() -> bool {
+		UNSIGNED_SUBMISSIONS.with(|v| *v.borrow())
+	}
+}
+
+thread_local! {
+	static FALLBACK_PRICES: RefCell<BTreeMap<CurrencyId, Price>> = RefCell::new(BTreeMap::new());
+}
+
+pub struct MockFallbackPriceSource;
+impl MockFallbackPriceSource {
+	pub fn set_price(currency_id: CurrencyId, price: Option<Price>) {
+		FALLBACK_PRICES.with(|prices| match price {
+			Some(price) => prices.borrow_mut().insert(currency_id, price),
+			None => prices.borrow_mut().remove(&currency_id),
+		});
+	}
+
+	fn reset() {
+		FALLBACK_PRICES.with(|prices| prices.borrow_mut().clear());
+	}
+}
+impl PriceProvider<CurrencyId> for MockFallbackPriceSource {
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		FALLBACK_PRICES.with(|prices| prices.borrow().get(&currency_id).copied())
+	}
+}
+
+thread_local! {
+	static ORACLE_QUORUM: RefCell<u32> = RefCell::new(1);
+}
+
+pub struct OracleQuorum;
+impl OracleQuorum {
+	pub fn set(quorum: u32) {
+		ORACLE_QUORUM.with(|v| *v.borrow_mut() = quorum);
+	}
+}
+impl Get<u32> for OracleQuorum {
+	fn get() -> u32 {
+		ORACLE_QUORUM.with(|v| *v.borrow())
+	}
+}
+
 parameter_types! {
 	pub const GetStp258NativeId: CurrencyId = DNAR;
+	pub const MaxMemoLength: u32 = 32;
+	pub const FeeMarkup: Permill = Permill::from_percent(10);
+	pub const MaxPriceStaleness: Blocknumber = 5;
+	pub const MintPeriod: Blocknumber = 10;
+	pub const MaxMintsPerPeriod: u32 = 2;
+	pub const TreasuryAccount: AccountId = TREASURY;
+	pub const SavingsPoolAccount: AccountId = SAVINGS;
+	pub const SerperPoolAccount: AccountId = SERPER_POOL;
+	pub const MaxBeneficiaries: u32 = 4;
+	pub const BondDuration: Blocknumber = 100;
+	pub const MaxBondRedemptions: u32 = 10;
+	pub const MaxSerperPoolMembers: u32 = 3;
+	pub const SerperCooldown: Blocknumber = 5;
+	pub const MaxContractionAttempts: u32 = 10;
+	pub const MaxExcludedAccounts: u32 = 2;
+	pub const MaxQuoteDeviation: Permill = Permill::from_percent(10);
+	pub const SerpTesModuleId: ModuleId = ModuleId(*b"set/serp");
+	pub const PriceEndpoint: &'static str = "http://localhost:8000/prices";
+	pub const OracleInterval: Blocknumber = 5;
+	pub const MaxSymbolLength: u32 = 8;
+	pub const MaxOracleSymbols: u32 = 3;
+	pub const UnsignedPriority: TransactionPriority = 1 << 20;
+	pub const UnsignedLongevity: Blocknumber = 5;
+	pub const MaxOracleDeviation: Permill = Permill::from_percent(5);
+	pub const RoundTimeout: Blocknumber = 3;
+	pub const MaxOperators: u32 = 4;
+	pub const MaxPriceJumpRejections: u32 = 2;
+	pub const OracleBond: Balance = 10;
+	pub const OracleSlash: Balance = 4;
+	pub const MaxCommitmentsPerCycle: u32 = 8;
+	pub const PoolSwapFee: Permill = Permill::from_percent(1);
+	pub const TwapPeriod: Blocknumber = 4;
 }
 
 impl stp258_standard::Config for Runtime {
@@ -139,15 +253,98 @@ impl stp258_standard::Config for Runtime {
 }
 pub type Stp258Native = Stp258NativeOf<Runtime>;
 pub type AdaptedStp258Asset = Stp258AssetAdapter<Runtime, PalletBalances, i64, u64>;
+pub type FeeAdapter = StableCurrencyFeeAdapter<Runtime, DepositFeesTo<Runtime, TreasuryAccount>>;
 
 impl Config for Runtime {
 	type Event = Event;
 	type Stp258Currency = Stp258Serp;
 	type Stp258Native = AdaptedStp258Asset;
 	type GetStp258NativeId = GetStp258NativeId;
+	type MaxMemoLength = MaxMemoLength;
+	type PriceSource = (
+		MockPriceSource,
+		MockFallbackPriceSource,
+		PoolTwapPrice<Runtime, MockPriceSource>,
+	);
+	type MaxPriceStaleness = MaxPriceStaleness;
+	type FeeMarkup = FeeMarkup;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type MintPeriod = MintPeriod;
+	type MaxMintsPerPeriod = MaxMintsPerPeriod;
+	type TreasuryAccount = TreasuryAccount;
+	type SavingsPoolAccount = SavingsPoolAccount;
+	type SerperPoolAccount = SerperPoolAccount;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type BondDuration = BondDuration;
+	type MaxBondRedemptions = MaxBondRedemptions;
+	type MaxSerperPoolMembers = MaxSerperPoolMembers;
+	type SerperCooldown = SerperCooldown;
+	type MaxExcludedAccounts = MaxExcludedAccounts;
+	type MaxQuoteDeviation = MaxQuoteDeviation;
+	type MaxContractionAttempts = MaxContractionAttempts;
+	type ModuleId = SerpTesModuleId;
+	type AuthorityId = crypto::OracleAuthId;
+	type PriceEndpoint = PriceEndpoint;
+	type OracleInterval = OracleInterval;
+	type UnsignedSubmissions = UnsignedSubmissions;
+	type UnsignedPriority = UnsignedPriority;
+	type UnsignedLongevity = UnsignedLongevity;
+	type OperatorOrigin = EnsureRoot<AccountId>;
+	type MaxOperators = MaxOperators;
+	type OracleQuorum = OracleQuorum;
+	type MaxPriceJumpRejections = MaxPriceJumpRejections;
+	type OracleBond = OracleBond;
+	type OracleSlash = OracleSlash;
+	type MaxCommitmentsPerCycle = MaxCommitmentsPerCycle;
+	type PoolSwapFee = PoolSwapFee;
+	type TwapPeriod = TwapPeriod;
+	type MaxOracleDeviation = MaxOracleDeviation;
+	type RoundTimeout = RoundTimeout;
+	type MaxSymbolLength = MaxSymbolLength;
+	type MaxOracleSymbols = MaxOracleSymbols;
 	type WeightInfo = ();
 }
 
+pub type Extrinsic = TestXt<Call, ()>;
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <MultiSignature as Verify>::Signer;
+	type Signature = MultiSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: <MultiSignature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub const TransactionByteFee: Balance = 0;
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+	type OnChargeTransaction = FeeAdapter;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
@@ -158,10 +355,11 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		Serp: serp::{Module, Call, Event<T>},
+		Serp: serp::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned},
 		Stp258Standard: stp258_standard::{Module, Call, Event<T>},
 		Stp258Serp: stp258_serp::{Module, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 	}
 );
 
@@ -169,6 +367,9 @@ pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const SERPER: AccountId = AccountId32::new([3u8; 32]);
 pub const SETTPAY: AccountId = AccountId32::new([4u8; 32]);
+pub const TREASURY: AccountId = AccountId32::new([5u8; 32]);
+pub const SAVINGS: AccountId = AccountId32::new([6u8; 32]);
+pub const SERPER_POOL: AccountId = AccountId32::new([7u8; 32]);
 
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
@@ -206,6 +407,11 @@ impl ExtBuilder {
 	}
 
 	pub fn build(self) -> sp_io::TestExternalities {
+		MockPriceSource::reset();
+		MockFallbackPriceSource::reset();
+		OracleQuorum::set(1);
+		UnsignedSubmissions::set(true);
+
 		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
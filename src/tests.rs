@@ -3,9 +3,16 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use codec::Decode;
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	weights::{DispatchInfo, Pays, PostDispatchInfo},
+};
 use mock::{Event, *};
-use sp_runtime::traits::BadOrigin;
+use sp_core::offchain::{testing, OffchainExt, TransactionPoolExt};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_runtime::{traits::{BadOrigin, BlakeTwo256}, MultiSigner};
+use std::sync::Arc;
 
 #[test]
 fn on_serp_block_should_work() {
@@ -275,3 +282,1826 @@ fn call_event_should_work() {
 		});
 }
 
+
+#[test]
+fn transfer_with_memo_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Serp::transfer_with_memo(
+				Some(ALICE).into(),
+				BOB,
+				JUSD,
+				10 * 1_000,
+				b"INV-0042".to_vec()
+			));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 110 * 1_000);
+
+			assert_ok!(Serp::transfer_native_currency_with_memo(
+				Some(ALICE).into(),
+				BOB,
+				50,
+				b"INV-0043".to_vec()
+			));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 50);
+			assert_eq!(Stp258Native::free_balance(&BOB), 150);
+
+			// both variants emit `Transferred` followed by the memo
+			let events = System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					Event::serp(event) => Some(event),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(
+				events,
+				vec![
+					crate::Event::Transferred(JUSD, ALICE, BOB, 10 * 1_000),
+					crate::Event::TransferredWithMemo(JUSD, ALICE, BOB, 10 * 1_000, b"INV-0042".to_vec()),
+					crate::Event::Transferred(DNAR, ALICE, BOB, 50),
+					crate::Event::TransferredWithMemo(DNAR, ALICE, BOB, 50, b"INV-0043".to_vec()),
+				]
+			);
+		});
+}
+
+#[test]
+fn transfer_with_memo_fails_if_memo_too_long() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Serp::transfer_with_memo(Some(ALICE).into(), BOB, JUSD, 10 * 1_000, vec![0u8; 33]),
+				Error::<Runtime>::MemoTooLong
+			);
+			assert_noop!(
+				Serp::transfer_native_currency_with_memo(Some(ALICE).into(), BOB, 50, vec![0u8; 33]),
+				Error::<Runtime>::MemoTooLong
+			);
+		});
+}
+
+#[test]
+fn set_fee_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Serp::set_fee_currency(Some(ALICE).into(), Some(DNAR)),
+				Error::<Runtime>::InvalidFeeCurrency
+			);
+			assert_ok!(Serp::set_fee_currency(Some(ALICE).into(), Some(JUSD)));
+			assert_eq!(Serp::fee_currency(&ALICE), Some(JUSD));
+			assert_ok!(Serp::set_fee_currency(Some(ALICE).into(), None));
+			assert_eq!(Serp::fee_currency(&ALICE), None);
+		});
+}
+
+#[test]
+fn fees_are_charged_and_refunded_in_stable_currency() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let call = Call::System(frame_system::Call::remark(vec![]));
+			let info = DispatchInfo::default();
+			let post_info = PostDispatchInfo {
+				actual_weight: None,
+				pays_fee: Pays::Yes,
+			};
+			assert_ok!(Serp::set_fee_currency(Some(ALICE).into(), Some(JUSD)));
+
+			// 10 DNAR at 4 JUSD per DNAR, plus 10% markup
+			assert_eq!(Serp::stable_fee(JUSD, 10), Some(44 * 1_000));
+			let liquidity = <FeeAdapter as OnChargeTransaction<Runtime>>::withdraw_fee(
+				&ALICE, &call, &info, 10, 0,
+			)
+			.unwrap();
+			assert_eq!(liquidity, Some((JUSD, 10, 44 * 1_000)));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 56 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 100);
+
+			// half of the weight was used, half of the fee comes back
+			assert_ok!(
+				<FeeAdapter as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+					&ALICE, &info, &post_info, 5, 0, liquidity,
+				)
+			);
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 78 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &TREASURY), 22 * 1_000);
+		});
+}
+
+#[test]
+fn fees_are_charged_in_native_currency_by_default() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let call = Call::System(frame_system::Call::remark(vec![]));
+			let info = DispatchInfo::default();
+			let post_info = PostDispatchInfo {
+				actual_weight: None,
+				pays_fee: Pays::Yes,
+			};
+
+			// the fee includes a tip of 2
+			let liquidity = <FeeAdapter as OnChargeTransaction<Runtime>>::withdraw_fee(
+				&ALICE, &call, &info, 10, 2,
+			)
+			.unwrap();
+			assert_eq!(Stp258Native::free_balance(&ALICE), 90);
+			assert_ok!(
+				<FeeAdapter as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+					&ALICE, &info, &post_info, 4, 2, liquidity,
+				)
+			);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 96);
+			assert_eq!(Stp258Native::free_balance(&TREASURY), 4);
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 100 * 1_000);
+		});
+}
+
+#[test]
+fn stable_fee_fails_without_price() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let call = Call::System(frame_system::Call::remark(vec![]));
+			MockPriceSource::set_price(JUSD, None);
+			assert_ok!(Serp::set_fee_currency(Some(ALICE).into(), Some(JUSD)));
+			assert_eq!(
+				<FeeAdapter as OnChargeTransaction<Runtime>>::withdraw_fee(
+					&ALICE,
+					&call,
+					&DispatchInfo::default(),
+					10,
+					0,
+				),
+				Err(InvalidTransaction::Payment.into())
+			);
+		});
+}
+
+#[test]
+fn mint_authority_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Serp::mint(Some(ALICE).into(), JUSD, BOB, 10 * 1_000),
+				Error::<Runtime>::NoMintAuthority
+			);
+			assert_noop!(
+				Serp::grant_mint_authority(Some(ALICE).into(), JUSD, ALICE, Some(50 * 1_000)),
+				BadOrigin
+			);
+			assert_ok!(Serp::grant_mint_authority(Origin::root(), JUSD, ALICE, Some(50 * 1_000)));
+
+			assert_ok!(Serp::mint(Some(ALICE).into(), JUSD, BOB, 30 * 1_000));
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 130 * 1_000);
+			assert_eq!(Serp::total_issuance(JUSD), 430 * 1_000);
+			let minted_event = Event::serp(crate::Event::Minted(JUSD, ALICE, BOB, 30 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == minted_event));
+
+			assert_noop!(
+				Serp::mint(Some(ALICE).into(), JUSD, BOB, 30 * 1_000),
+				Error::<Runtime>::MintCapExceeded
+			);
+
+			System::set_block_number(5);
+			assert_ok!(Serp::mint(Some(ALICE).into(), JUSD, BOB, 20 * 1_000));
+
+			// the cap applies to any `MintPeriod` consecutive blocks
+			System::set_block_number(11);
+			assert_ok!(Serp::mint(Some(ALICE).into(), JUSD, BOB, 30 * 1_000));
+			System::set_block_number(12);
+			assert_noop!(
+				Serp::mint(Some(ALICE).into(), JUSD, BOB, 1_000),
+				Error::<Runtime>::MintCapExceeded
+			);
+			System::set_block_number(15);
+			assert_ok!(Serp::mint(Some(ALICE).into(), JUSD, BOB, 10 * 1_000));
+			assert_ok!(Serp::mint(Some(ALICE).into(), JUSD, BOB, 5 * 1_000));
+			System::set_block_number(16);
+			assert_noop!(
+				Serp::mint(Some(ALICE).into(), JUSD, BOB, 1_000),
+				Error::<Runtime>::TooManyMints
+			);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 195 * 1_000);
+
+			assert_ok!(Serp::burn(Some(ALICE).into(), JUSD, 20 * 1_000));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 80 * 1_000);
+			assert_eq!(Serp::total_issuance(JUSD), 475 * 1_000);
+			let burned_event = Event::serp(crate::Event::Burned(JUSD, ALICE, 20 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == burned_event));
+
+			assert_ok!(Serp::revoke_mint_authority(Origin::root(), JUSD, ALICE));
+			assert_noop!(
+				Serp::burn(Some(ALICE).into(), JUSD, 10 * 1_000),
+				Error::<Runtime>::NoMintAuthority
+			);
+		});
+}
+
+#[test]
+fn supply_ceiling_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Serp::set_issuance_bounds(Origin::root(), JUSD, Some(410 * 1_000), Some(400 * 1_000)),
+				Error::<Runtime>::InvalidIssuanceBounds
+			);
+			assert_ok!(Serp::set_issuance_bounds(Origin::root(), JUSD, None, Some(410 * 1_000)));
+			assert_eq!(Serp::max_issuance(JUSD), Some(410 * 1_000));
+
+			assert_noop!(
+				<Serp as Stp258Currency<AccountId>>::deposit(JUSD, &ALICE, 20 * 1_000),
+				Error::<Runtime>::SupplyCeilingReached
+			);
+			assert_noop!(
+				Serp::update_balance(Origin::root(), ALICE, JUSD, 20 * 1_000),
+				Error::<Runtime>::SupplyCeilingReached
+			);
+			assert_noop!(
+				Serp::expand_supply(DNAR, JUSD, 20 * 1_000, 4_000),
+				Error::<Runtime>::SupplyCeilingReached
+			);
+			assert_ok!(<Serp as Stp258Currency<AccountId>>::deposit(JUSD, &ALICE, 10 * 1_000));
+			assert_eq!(Serp::total_issuance(JUSD), 410 * 1_000);
+		});
+}
+
+#[test]
+fn supply_floor_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Serp::set_issuance_bounds(Origin::root(), JUSD, Some(390 * 1_000), None));
+			assert_eq!(Serp::min_issuance(JUSD), Some(390 * 1_000));
+
+			assert_noop!(
+				<Serp as Stp258Currency<AccountId>>::withdraw(JUSD, &ALICE, 20 * 1_000),
+				Error::<Runtime>::SupplyFloorReached
+			);
+			assert_noop!(
+				Serp::update_balance(Origin::root(), ALICE, JUSD, -20 * 1_000),
+				Error::<Runtime>::SupplyFloorReached
+			);
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 20 * 1_000, 4_000),
+				Error::<Runtime>::SupplyFloorReached
+			);
+
+			// slashing stops at the floor and reports the remainder
+			assert_eq!(Serp::slash(JUSD, &ALICE, 20 * 1_000), 10 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Serp::total_issuance(JUSD), 390 * 1_000);
+
+			assert_ok!(Serp::set_issuance_bounds(Origin::root(), JUSD, None, None));
+			assert_eq!(Serp::min_issuance(JUSD), None);
+			assert_ok!(<Serp as Stp258Currency<AccountId>>::withdraw(JUSD, &ALICE, 20 * 1_000));
+		});
+}
+
+#[test]
+fn set_expansion_distribution_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Serp::set_expansion_distribution(
+				Origin::root(),
+				JUSD,
+				vec![
+					(Beneficiary::Treasury, Perbill::from_percent(50)),
+					(Beneficiary::SavingsPool, Perbill::from_percent(40)),
+				]
+			),
+			Error::<Runtime>::InvalidDistribution
+		);
+		assert_noop!(
+			Serp::set_expansion_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(Beneficiary::Treasury, Perbill::from_percent(20)); 5]
+			),
+			Error::<Runtime>::TooManyBeneficiaries
+		);
+		assert_ok!(Serp::set_expansion_distribution(
+			Origin::root(),
+			JUSD,
+			vec![(Beneficiary::Treasury, Perbill::from_percent(100))]
+		));
+		assert_eq!(Serp::expansion_distribution(JUSD).len(), 1);
+		assert_ok!(Serp::set_expansion_distribution(Origin::root(), JUSD, vec![]));
+		assert!(Serp::expansion_distribution(JUSD).is_empty());
+	});
+}
+
+#[test]
+fn expand_supply_follows_distribution() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::set_expansion_distribution(
+				Origin::root(),
+				JUSD,
+				vec![
+					(Beneficiary::Treasury, Perbill::from_percent(20)),
+					(Beneficiary::SavingsPool, Perbill::from_percent(30)),
+					(Beneficiary::SerperPool, Perbill::from_percent(25)),
+					(Beneficiary::Account(BOB), Perbill::from_percent(25)),
+				]
+			));
+
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_eq!(Serp::total_issuance(JUSD), 440 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &TREASURY), 8 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &SAVINGS), 12 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &SERPER_POOL), 10 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 110 * 1_000);
+
+			let distributed_event = Event::serp(crate::Event::ExpansionDistributed(
+				JUSD,
+				vec![
+					(TREASURY, 8 * 1_000),
+					(SAVINGS, 12 * 1_000),
+					(SERPER_POOL, 10 * 1_000),
+					(BOB, 10 * 1_000),
+				],
+			));
+			assert!(System::events().iter().any(|record| record.event == distributed_event));
+		});
+}
+
+#[test]
+fn set_bond_curve_and_contraction_method_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Bonds),
+			Error::<Runtime>::BondCurveNotSet
+		);
+		assert_noop!(
+			Serp::set_bond_curve(
+				Origin::root(),
+				JUSD,
+				BondPriceCurve {
+					start_price: Perbill::from_percent(90),
+					end_price: Perbill::from_percent(80),
+				}
+			),
+			Error::<Runtime>::InvalidBondCurve
+		);
+		assert_ok!(Serp::set_bond_curve(
+			Origin::root(),
+			JUSD,
+			BondPriceCurve {
+				start_price: Perbill::from_percent(80),
+				end_price: Perbill::from_percent(100),
+			}
+		));
+		assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Bonds));
+		assert_eq!(Serp::contraction_method(JUSD), ContractionMethod::Bonds);
+		assert_eq!(Serp::contraction_method(SETT), ContractionMethod::Stp258);
+	});
+}
+
+#[test]
+fn bonds_are_sold_on_contraction_and_redeemed_on_expansion() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::set_bond_curve(
+				Origin::root(),
+				JUSD,
+				BondPriceCurve {
+					start_price: Perbill::from_percent(80),
+					end_price: Perbill::from_percent(100),
+				}
+			));
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Bonds));
+			LastSerpPrices::<Runtime>::insert(JUSD, FixedU128::saturating_from_rational(90, 100));
+
+			assert_noop!(
+				Serp::buy_bonds(Some(ALICE).into(), JUSD, 10 * 1_000),
+				Error::<Runtime>::NoBondCapacity
+			);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_eq!(Serp::bond_window(JUSD).capacity, 40 * 1_000);
+			let opened_event = Event::serp(crate::Event::BondCapacityOpened(JUSD, 40 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == opened_event));
+			assert!(!System::events()
+				.iter()
+				.any(|record| matches!(record.event, Event::serp(crate::Event::SerpedDownSupply(..)))));
+
+			// the first half of the window sells at 80%
+			assert_ok!(Serp::buy_bonds(Some(ALICE).into(), JUSD, 20 * 1_000));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 80 * 1_000);
+			assert_eq!(Serp::bonds_of(JUSD, &ALICE), 25 * 1_000);
+			let contracted_event = Event::serp(crate::Event::SerpedDownSupply(JUSD, 20 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == contracted_event));
+
+			// the rest sells at 90%, and no more than the window is sold
+			assert_ok!(Serp::buy_bonds(Some(BOB).into(), JUSD, 30 * 1_000));
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 80 * 1_000);
+			assert_eq!(Serp::bonds_of(JUSD, &BOB), 22_222);
+			assert_eq!(Serp::total_issuance(JUSD), 360 * 1_000);
+			assert_eq!(Serp::bond_window(JUSD), Default::default());
+
+			// bonds are redeemed oldest first
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 30 * 1_000, 4_000));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 105 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 85 * 1_000);
+			assert_eq!(Serp::bonds_of(JUSD, &ALICE), 0);
+			assert_eq!(Serp::bonds_of(JUSD, &BOB), 17_222);
+			assert_eq!(Serp::total_issuance(JUSD), 390 * 1_000);
+
+			let redeemed_event = Event::serp(crate::Event::BondRedeemed(JUSD, BOB, 5 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == redeemed_event));
+		});
+}
+
+#[test]
+fn bonds_are_not_sold_above_peg() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::set_bond_curve(
+				Origin::root(),
+				JUSD,
+				BondPriceCurve {
+					start_price: Perbill::from_percent(80),
+					end_price: Perbill::from_percent(100),
+				}
+			));
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Bonds));
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_noop!(
+				Serp::buy_bonds(Some(ALICE).into(), JUSD, 10 * 1_000),
+				Error::<Runtime>::BondsNotOnSale
+			);
+
+			LastSerpPrices::<Runtime>::insert(JUSD, FixedU128::saturating_from_rational(90, 100));
+			assert_ok!(Serp::buy_bonds(Some(ALICE).into(), JUSD, 10 * 1_000));
+
+			// an expansion closes what is left of the window
+			LastSerpPrices::<Runtime>::insert(JUSD, FixedU128::saturating_from_rational(110, 100));
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			assert_eq!(Serp::bond_window(JUSD), Default::default());
+			LastSerpPrices::<Runtime>::insert(JUSD, FixedU128::saturating_from_rational(90, 100));
+			assert_noop!(
+				Serp::buy_bonds(Some(BOB).into(), JUSD, 10 * 1_000),
+				Error::<Runtime>::NoBondCapacity
+			);
+		});
+}
+
+#[test]
+fn expired_bonds_are_not_redeemed() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::set_bond_curve(
+				Origin::root(),
+				JUSD,
+				BondPriceCurve {
+					start_price: Perbill::from_percent(80),
+					end_price: Perbill::from_percent(100),
+				}
+			));
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Bonds));
+			LastSerpPrices::<Runtime>::insert(JUSD, FixedU128::saturating_from_rational(90, 100));
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_ok!(Serp::buy_bonds(Some(ALICE).into(), JUSD, 20 * 1_000));
+
+			System::set_block_number(101);
+			assert_ok!(Serp::set_expansion_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(Beneficiary::Treasury, Perbill::from_percent(100))]
+			));
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 80 * 1_000);
+			assert_eq!(Serp::bonds_of(JUSD, &ALICE), 0);
+			assert_eq!(Serp::free_balance(JUSD, &TREASURY), 10 * 1_000);
+
+			let expired_event = Event::serp(crate::Event::BondExpired(JUSD, ALICE, 25 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == expired_event));
+		});
+}
+
+#[test]
+fn contraction_auction_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Auction),
+				Error::<Runtime>::AuctionParamsNotSet
+			);
+			assert_ok!(Serp::set_auction_params(
+				Origin::root(),
+				JUSD,
+				AuctionParams {
+					start_premium: Perbill::from_percent(10),
+					decay_per_block: Perbill::from_percent(2),
+					max_discount: Perbill::from_percent(5),
+					duration: 10,
+				}
+			));
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Auction));
+
+			assert_noop!(
+				Serp::bid(Some(ALICE).into(), JUSD, 22 * 1_000),
+				Error::<Runtime>::AuctionNotOpen
+			);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_eq!(Serp::auction(JUSD).unwrap().end, 11);
+
+			// bids are paid out of the reserve, never minted
+			assert_noop!(
+				Serp::bid(Some(ALICE).into(), JUSD, 22 * 1_000),
+				Error::<Runtime>::InsufficientReserve
+			);
+			assert_ok!(Serp::fund_reserve(Some(SETTPAY).into(), JUSD, 20));
+
+			// the auction starts at a 10% premium over the quote price
+			assert_ok!(Serp::bid(Some(ALICE).into(), JUSD, 22 * 1_000));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 78 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 105);
+			let contracted_event = Event::serp(crate::Event::SerpedDownSupply(JUSD, 22 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == contracted_event));
+
+			// and decays by 2% of the quote price per block
+			System::set_block_number(6);
+			assert_ok!(Serp::bid(Some(BOB).into(), JUSD, 8 * 1_000));
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 92 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&BOB), 102);
+			assert_eq!(Serp::auction(JUSD).unwrap().remaining, 10 * 1_000);
+			assert_eq!(Serp::total_issuance(JUSD), 370 * 1_000);
+
+			// the unfilled amount is carried into the next auction
+			Serp::on_initialize(12);
+			assert_eq!(Serp::auction(JUSD), None);
+			assert_eq!(Serp::contraction_debt(JUSD), 10 * 1_000);
+			let closed_event = Event::serp(crate::Event::AuctionClosed(JUSD, 40 * 1_000, 10 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == closed_event));
+
+			System::set_block_number(12);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			assert_eq!(Serp::auction(JUSD).unwrap().remaining, 20 * 1_000);
+			assert_eq!(Serp::contraction_debt(JUSD), 0);
+
+			// a top-up restarts the auction
+			System::set_block_number(14);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 5 * 1_000, 4_000));
+			let auction = Serp::auction(JUSD).unwrap();
+			assert_eq!((auction.remaining, auction.start, auction.end), (25 * 1_000, 14, 24));
+
+			// the price never falls below the maximum discount
+			System::set_block_number(22);
+			assert_ok!(Serp::bid(Some(BOB).into(), JUSD, 7_600));
+			assert_eq!(Stp258Native::free_balance(&BOB), 104);
+
+			Serp::on_initialize(23);
+			assert_eq!(Serp::auction(JUSD).unwrap().remaining, 17_400);
+			Serp::on_initialize(25);
+			assert_eq!(Serp::auction(JUSD), None);
+			assert_eq!(Serp::contraction_debt(JUSD), 17_400);
+
+			// an expansion closes the auction and expands by what it leaves over
+			System::set_block_number(25);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 5 * 1_000, 4_000));
+			assert_eq!(Serp::auction(JUSD).unwrap().remaining, 22_400);
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 30 * 1_000, 4_000));
+			assert_eq!(Serp::auction(JUSD), None);
+			let closed_event = Event::serp(crate::Event::AuctionClosed(JUSD, 22_400, 22_400));
+			assert!(System::events().iter().any(|record| record.event == closed_event));
+			assert_eq!(Serp::contraction_debt(JUSD), 0);
+			assert_eq!(Serp::total_issuance(JUSD), 370 * 1_000);
+		});
+}
+
+#[test]
+fn serper_pool_membership_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::join_serper_pool(Some(ALICE).into(), JUSD, 60 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(BOB).into(), JUSD, 20 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(SERPER).into(), JUSD, 20 * 1_000));
+			assert_noop!(
+				Serp::join_serper_pool(Some(SETTPAY).into(), JUSD, 20 * 1_000),
+				Error::<Runtime>::SerperPoolFull
+			);
+			assert_eq!(Serp::serper_pool(JUSD).total, 100 * 1_000);
+			assert_eq!(Serp::reserved_balance(JUSD, &ALICE), 60 * 1_000);
+
+			assert_noop!(
+				Serp::leave_serper_pool(Some(BOB).into(), JUSD, 30 * 1_000),
+				Error::<Runtime>::InsufficientSerperStake
+			);
+			assert_ok!(Serp::leave_serper_pool(Some(BOB).into(), JUSD, 20 * 1_000));
+			assert_eq!(Serp::serper_pool(JUSD).members, 2);
+			assert_eq!(Serp::serper_unbonding(JUSD, &BOB), Some((20 * 1_000, 6)));
+
+			assert_noop!(
+				Serp::withdraw_unbonded(Some(BOB).into(), JUSD),
+				Error::<Runtime>::SerperCooldownActive
+			);
+			System::set_block_number(6);
+			assert_ok!(Serp::withdraw_unbonded(Some(BOB).into(), JUSD));
+			assert_eq!(Serp::reserved_balance(JUSD, &BOB), 0);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 100 * 1_000);
+			assert_noop!(
+				Serp::withdraw_unbonded(Some(BOB).into(), JUSD),
+				Error::<Runtime>::NothingToWithdraw
+			);
+		});
+}
+
+#[test]
+fn serper_pool_contraction_is_pro_rata() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::SerperPool));
+			assert_ok!(Serp::join_serper_pool(Some(ALICE).into(), JUSD, 60 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(BOB).into(), JUSD, 20 * 1_000));
+
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 32 * 1_000, 4_000),
+				Error::<Runtime>::InsufficientReserve
+			);
+			assert_ok!(Serp::fund_reserve(Some(SETTPAY).into(), JUSD, 20));
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 90 * 1_000, 4_000),
+				Error::<Runtime>::InsufficientSerperPool
+			);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 32 * 1_000, 4_000));
+			assert_eq!(Serp::reserved_balance(JUSD, &ALICE), 36 * 1_000);
+			assert_eq!(Serp::reserved_balance(JUSD, &BOB), 12 * 1_000);
+			assert_eq!(Serp::serper_stake(JUSD, &ALICE), 36 * 1_000);
+			assert_eq!(Serp::serper_pool(JUSD).total, 48 * 1_000);
+			assert_eq!(Serp::total_issuance(JUSD), 368 * 1_000);
+
+			// members are paid native currency at the quote price
+			assert_eq!(Stp258Native::free_balance(&ALICE), 106);
+			assert_eq!(Stp258Native::free_balance(&BOB), 102);
+		});
+}
+
+#[test]
+fn serper_pool_contraction_burns_only_what_is_reserved() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::SerperPool));
+			assert_noop!(
+				Serp::join_serper_pool(Some(SERPER).into(), JUSD, 0),
+				Error::<Runtime>::InsufficientSerperStake
+			);
+			assert_ok!(Serp::join_serper_pool(Some(ALICE).into(), JUSD, 60 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(BOB).into(), JUSD, 20 * 1_000));
+			assert_ok!(Serp::fund_reserve(Some(SETTPAY).into(), JUSD, 20));
+			assert_eq!(<Serp as Stp258CurrencyReservable<AccountId>>::slash_reserved(JUSD, &BOB, 16 * 1_000), 0);
+
+			// BOB's share is 8 JUSD, of which only 4 are still reserved
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 32 * 1_000, 4_000));
+			assert_eq!(Serp::serper_stake(JUSD, &BOB), 16 * 1_000);
+			assert_eq!(Serp::serper_pool(JUSD).total, 52 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&BOB), 101);
+			let bob_event = Event::serp(crate::Event::SerperContracted(JUSD, BOB, 4 * 1_000, 1));
+			assert!(System::events().iter().any(|record| record.event == bob_event));
+			let contracted_event = Event::serp(crate::Event::SerpedDownSupply(JUSD, 28 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == contracted_event));
+		});
+}
+
+#[test]
+fn serper_pool_rewards_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Serp::set_expansion_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(Beneficiary::SerperPool, Perbill::from_percent(100))]
+			));
+			assert_ok!(Serp::join_serper_pool(Some(ALICE).into(), JUSD, 10 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(BOB).into(), JUSD, 20 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(SERPER).into(), JUSD, 40 * 1_000));
+
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 7 * 1_000, 4_000));
+			assert_eq!(Serp::reward_per_share(JUSD), FixedU128::saturating_from_rational(1, 10));
+			assert_eq!(Serp::free_balance(JUSD, &SERPER_POOL), 7 * 1_000);
+
+			assert_ok!(Serp::claim_rewards(Some(ALICE).into(), JUSD));
+			assert_ok!(Serp::claim_rewards(Some(BOB).into(), JUSD));
+			assert_ok!(Serp::claim_rewards(Some(SERPER).into(), JUSD));
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 91 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 82 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &SERPER), 64 * 1_000);
+			assert_eq!(Serp::free_balance(JUSD, &SERPER_POOL), 0);
+
+			assert_noop!(
+				Serp::claim_rewards(Some(ALICE).into(), JUSD),
+				Error::<Runtime>::NoRewards
+			);
+		});
+}
+
+#[test]
+fn serper_pool_rewards_are_conserved() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Serp::set_expansion_distribution(
+				Origin::root(),
+				JUSD,
+				vec![(Beneficiary::SerperPool, Perbill::from_percent(100))]
+			));
+			assert_ok!(Serp::join_serper_pool(Some(ALICE).into(), JUSD, 10 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(BOB).into(), JUSD, 20 * 1_000));
+			assert_ok!(Serp::join_serper_pool(Some(SERPER).into(), JUSD, 40 * 1_000));
+
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 7_001, 4_000));
+			assert_ok!(Serp::join_serper_pool(Some(BOB).into(), JUSD, 10 * 1_000));
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 9_999, 4_000));
+			let distributed = 17 * 1_000;
+
+			let members = [ALICE, BOB, SERPER];
+			let before = members.iter().map(|who| Serp::free_balance(JUSD, who)).collect::<Vec<_>>();
+			for who in members.iter() {
+				assert_ok!(Serp::claim_rewards(Some(who.clone()).into(), JUSD));
+			}
+			let claimed: Balance = members
+				.iter()
+				.zip(before)
+				.map(|(who, before)| Serp::free_balance(JUSD, who) - before)
+				.sum();
+
+			// nothing is paid out twice, and at most one unit per member is
+			// lost to rounding
+			assert!(claimed <= distributed);
+			assert!(distributed - claimed < members.len() as Balance);
+			assert_eq!(claimed, 16_999);
+			assert_eq!(Serp::free_balance(JUSD, &SERPER_POOL), distributed - claimed);
+		});
+}
+
+#[test]
+fn collateral_ratio_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_eq!(Serp::collateral_ratio(JUSD), Some(FixedU128::zero()));
+			assert_ok!(Serp::fund_reserve(Some(ALICE).into(), JUSD, 50));
+			assert_eq!(Serp::reserve(JUSD), 50);
+			assert_eq!(Stp258Native::free_balance(&Serp::reserve_account(JUSD)), 50);
+			assert_eq!(Serp::collateral_ratio(JUSD), Some(FixedU128::saturating_from_rational(1, 2)));
+
+			MockPriceSource::set_price(DNAR, None);
+			assert_eq!(Serp::collateral_ratio(JUSD), None);
+		});
+}
+
+#[test]
+fn contraction_respects_min_collateral_ratio() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let min_ratio = FixedU128::saturating_from_rational(45, 100);
+			assert_noop!(
+				Serp::set_min_collateral_ratio(Some(ALICE).into(), JUSD, Some(min_ratio)),
+				BadOrigin
+			);
+			assert_ok!(Serp::set_min_collateral_ratio(Origin::root(), JUSD, Some(min_ratio)));
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::SerperPool));
+			assert_ok!(Serp::fund_reserve(Some(ALICE).into(), JUSD, 50));
+			assert_ok!(Serp::join_serper_pool(Some(ALICE).into(), JUSD, 60 * 1_000));
+
+			// paying 10 DNAR out of the reserve would leave it at 160 / 360
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000),
+				Error::<Runtime>::CollateralRatioTooLow
+			);
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 20 * 1_000, 4_000));
+			assert_eq!(Serp::reserve(JUSD), 45);
+			assert_eq!(Stp258Native::free_balance(&Serp::reserve_account(JUSD)), 45);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 55);
+
+			// auctions are checked against their payout at the maximum discount,
+			// 5 DNAR for 19 JUSD rather than 4 at the quote price
+			assert_ok!(Serp::set_auction_params(
+				Origin::root(),
+				JUSD,
+				AuctionParams {
+					start_premium: Perbill::from_percent(10),
+					decay_per_block: Perbill::from_percent(2),
+					max_discount: Perbill::from_percent(5),
+					duration: 10,
+				}
+			));
+			assert_ok!(Serp::set_contraction_method(Origin::root(), JUSD, ContractionMethod::Auction));
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 19 * 1_000, 4_000),
+				Error::<Runtime>::CollateralRatioTooLow
+			);
+
+			// once the last good price is stale as well
+			MockPriceSource::set_price(JUSD, None);
+			System::set_block_number(6);
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 1_000, 4_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+		});
+}
+
+#[test]
+fn partial_contraction_records_debt() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Serp::reserve(JUSD, &SERPER, 30 * 1_000));
+
+			// only 30 of the 40 requested can be filled by the serper
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_eq!(Serp::total_issuance(JUSD), 370 * 1_000);
+			assert_eq!(Serp::contraction_debt(JUSD), 10 * 1_000);
+			let partial_event = Event::serp(crate::Event::ContractionPartiallyFilled(JUSD, 40 * 1_000, 30 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == partial_event));
+			let debt_event = Event::serp(crate::Event::ContractionDebtChanged(JUSD, 10 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == debt_event));
+			let contracted_event = Event::serp(crate::Event::SerpedDownSupply(JUSD, 30 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == contracted_event));
+
+			// the debt counts towards the supply floor
+			assert_ok!(Serp::set_issuance_bounds(Origin::root(), JUSD, Some(357 * 1_000), None));
+			assert_noop!(
+				Serp::contract_supply(DNAR, JUSD, 5 * 1_000, 4_000),
+				Error::<Runtime>::SupplyFloorReached
+			);
+			assert_ok!(Serp::set_issuance_bounds(Origin::root(), JUSD, None, None));
+
+			// the debt is retried first on the next round
+			assert_ok!(Stp258Serp::reserve(JUSD, &SERPER, 20 * 1_000));
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 5 * 1_000, 4_000));
+			assert_eq!(Serp::total_issuance(JUSD), 355 * 1_000);
+			assert_eq!(Serp::contraction_debt(JUSD), 0);
+			assert_eq!(Stp258Serp::reserved_balance(JUSD, &SERPER), 5 * 1_000);
+		});
+}
+
+#[test]
+fn expansion_nets_contraction_debt() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Serp::reserve(JUSD, &SERPER, 30 * 1_000));
+			assert_ok!(Serp::contract_supply(DNAR, JUSD, 40 * 1_000, 4_000));
+			assert_eq!(Serp::total_issuance(JUSD), 370 * 1_000);
+			assert_eq!(Serp::contraction_debt(JUSD), 10 * 1_000);
+
+			// an expansion smaller than the debt only pays it down
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 4 * 1_000, 4_000));
+			assert_eq!(Serp::total_issuance(JUSD), 370 * 1_000);
+			assert_eq!(Serp::contraction_debt(JUSD), 6 * 1_000);
+			assert!(!System::events()
+				.iter()
+				.any(|record| matches!(record.event, Event::serp(crate::Event::SerpedUpSupply(..)))));
+
+			// and a larger one expands by what is left over
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			assert_eq!(Serp::total_issuance(JUSD), 374 * 1_000);
+			assert_eq!(Serp::contraction_debt(JUSD), 0);
+			let expanded_event = Event::serp(crate::Event::SerpedUpSupply(JUSD, 4 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == expanded_event));
+		});
+}
+
+#[test]
+fn supply_change_carries_rounding_remainder() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Serp::update_balance(Origin::root(), ALICE, JUSD, 3));
+			let issuance = Serp::total_issuance(JUSD);
+
+			// computing the change leaves the remainder alone
+			let change = Serp::supply_change(JUSD, 1_001);
+			assert_eq!(Serp::supply_change(JUSD, 1_001), change);
+			assert_eq!(Serp::supply_change_remainder(JUSD), FixedU128::zero());
+
+			// each round's exact change is a thousandth of the circulating supply,
+			// and what truncation drops is carried into the next applied round
+			let mut exact = 0u128;
+			for _ in 0..1_000 {
+				exact += Serp::circulating_supply(JUSD) as u128;
+				let change = Serp::supply_change(JUSD, 1_001);
+				let before = Serp::total_issuance(JUSD);
+				assert_ok!(Serp::serp_elast(JUSD, 1_001, DNAR, 4_000));
+				assert_eq!(Serp::total_issuance(JUSD), before + change);
+			}
+			let applied = (Serp::total_issuance(JUSD) - issuance) as u128;
+			assert_eq!(
+				Serp::supply_change_remainder(JUSD),
+				FixedU128::saturating_from_rational(exact - applied * 1_000, 1_000)
+			);
+			assert!(exact - applied * 1_000 < 1_000);
+		});
+}
+
+#[test]
+fn quoted_price_normalises_base_units() {
+	ExtBuilder::default().build().execute_with(|| {
+		let sett_quote = Serp::quoted_price(DNAR, SETT, 40_000).unwrap();
+		let jusd_quote = Serp::quoted_price(DNAR, JUSD, 4_000).unwrap();
+		assert_eq!(sett_quote.price, jusd_quote.price);
+		assert_eq!(jusd_quote.price, FixedU128::saturating_from_integer(4));
+		assert_eq!(Serp::quote_per_base_unit(&sett_quote), 40_000);
+
+		assert_eq!(Serp::base_to_quote(&jusd_quote, 3), Some(12_000));
+		assert_eq!(Serp::quote_to_base(&sett_quote, 120_000), Some(3));
+		assert_eq!(Serp::quote_to_base(&jusd_quote, 3_999), Some(0));
+		assert_eq!(Serp::reference_price(SETT, 11_000), Serp::reference_price(JUSD, 1_100));
+		assert_eq!(Serp::reference_price(JUSD, 1_100), Some(FixedU128::saturating_from_rational(11, 10)));
+
+		assert_noop!(
+			Serp::contract_supply(DNAR, JUSD, 10 * 1_000, 0),
+			Error::<Runtime>::InvalidQuotePrice
+		);
+		assert_noop!(
+			Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 0),
+			Error::<Runtime>::InvalidQuotePrice
+		);
+	});
+}
+
+#[test]
+fn quotes_off_the_price_source_are_rejected() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let rejected_event = Event::serp(crate::Event::QuoteRejected(JUSD, 4_500, 4_000));
+			assert_err!(
+				Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_500),
+				Error::<Runtime>::QuoteDeviationTooLarge
+			);
+			assert!(System::events().iter().any(|record| record.event == rejected_event));
+
+			// SERP rounds fail on off-market quotes alike
+			System::reset_events();
+			assert_err!(
+				Serp::serp_elast(JUSD, 1_100, DNAR, 4_500),
+				Error::<Runtime>::QuoteDeviationTooLarge
+			);
+			assert!(System::events().iter().any(|record| record.event == rejected_event));
+			assert_err!(
+				Serp::contract_supply(DNAR, SETT, 10 * 10_000, 35_000),
+				Error::<Runtime>::QuoteDeviationTooLarge
+			);
+			let rejected_event = Event::serp(crate::Event::QuoteRejected(SETT, 35_000, 40_000));
+			assert!(System::events().iter().any(|record| record.event == rejected_event));
+			assert_eq!(Serp::total_issuance(JUSD), 400 * 1_000);
+
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_300));
+			assert_eq!(Serp::total_issuance(JUSD), 410 * 1_000);
+
+			// without a price for the native currency
+			MockPriceSource::set_price(DNAR, None);
+			System::set_block_number(7);
+			assert_noop!(
+				Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+
+			// a full window of the pool's rate takes over from the price source
+			assert_ok!(Serp::add_liquidity(Some(ALICE).into(), JUSD, 10, 45 * 1_000));
+			System::set_block_number(11);
+			assert_err!(
+				Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000),
+				Error::<Runtime>::QuoteDeviationTooLarge
+			);
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_500));
+			assert_eq!(Serp::total_issuance(JUSD), 420 * 1_000);
+		});
+}
+
+#[test]
+fn circulating_supply_excludes_accounts() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Serp::exclude_from_circulation(Some(ALICE).into(), JUSD, SERPER),
+				BadOrigin
+			);
+			assert_ok!(Serp::exclude_from_circulation(Origin::root(), JUSD, SERPER));
+			assert_ok!(Serp::reserve(JUSD, &SERPER, 40 * 1_000));
+			assert_noop!(
+				Serp::exclude_from_circulation(Origin::root(), JUSD, SERPER),
+				Error::<Runtime>::AlreadyExcluded
+			);
+			assert_ok!(Serp::exclude_from_circulation(Origin::root(), JUSD, SETTPAY));
+			assert_noop!(
+				Serp::exclude_from_circulation(Origin::root(), JUSD, BOB),
+				Error::<Runtime>::TooManyExcludedAccounts
+			);
+
+			// reserved balances of excluded accounts do not circulate either
+			assert_eq!(Serp::circulating_supply(JUSD), 200 * 1_000);
+			assert_eq!(Serp::circulating_supply(SETT), 400 * 10_000);
+			assert_eq!(Serp::supply_change(JUSD, 1_100), 20 * 1_000);
+			assert_eq!(Serp::supply_change(JUSD, 900), 20 * 1_000);
+			assert_eq!(
+				Serp::supply_adjustment(JUSD, FixedU128::saturating_from_rational(9, 10)),
+				Some((SupplyAdjustment::Contract(20 * 1_000), FixedU128::zero()))
+			);
+
+			assert_ok!(Serp::include_in_circulation(Origin::root(), JUSD, SETTPAY));
+			assert_noop!(
+				Serp::include_in_circulation(Origin::root(), JUSD, SETTPAY),
+				Error::<Runtime>::NotExcluded
+			);
+			assert_eq!(Serp::excluded_accounts(JUSD), vec![SERPER]);
+			assert_eq!(Serp::circulating_supply(JUSD), 300 * 1_000);
+		});
+}
+
+#[test]
+fn submit_prices_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(3);
+		Operators::<Runtime>::put(vec![ALICE]);
+		assert_noop!(
+			Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD-TOO-LONG".to_vec())),
+			Error::<Runtime>::SymbolTooLong
+		);
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), DNAR, Some(b"DNAR".to_vec())));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), SETT, Some(b"SETT".to_vec())));
+		assert_noop!(
+			Serp::set_oracle_symbol(Origin::root(), 4, Some(b"EUSD".to_vec())),
+			Error::<Runtime>::TooManyOracleSymbols
+		);
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), SETT, None));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+
+		let price = FixedU128::saturating_from_rational(101, 100);
+		assert_noop!(
+			Serp::submit_prices(Some(BOB).into(), vec![(JUSD, price)]),
+			Error::<Runtime>::NotOperator
+		);
+		assert_noop!(
+			Serp::submit_prices(Some(ALICE).into(), vec![(SETT, price)]),
+			Error::<Runtime>::UnknownOracleCurrency
+		);
+		assert_ok!(Serp::submit_prices(Some(ALICE).into(), vec![(JUSD, price)]));
+		assert_eq!(Serp::oracle_price(JUSD), Some((price, 3)));
+		assert_eq!(<Serp as PriceProvider<CurrencyId>>::get_price(JUSD), Some(price));
+		assert_eq!(<Serp as PriceProvider<CurrencyId>>::get_price(SETT), None);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_fetched_prices() {
+	const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	SyncCryptoStore::sr25519_generate_new(&keystore, crate::KEY_TYPE, Some(&format!("{}/serp", PHRASE))).unwrap();
+	offchain_state.write().expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:8000/prices".into(),
+		response: Some(br#"{"DNAR": 4.25, "JUSD": 1.01, "SETT": "n/a"}"#.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+	ext.execute_with(|| {
+		UnsignedSubmissions::set(false);
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), DNAR, Some(b"DNAR".to_vec())));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), SETT, Some(b"SETT".to_vec())));
+
+		// prices are only fetched every `OracleInterval` blocks
+		Serp::offchain_worker(4);
+		assert!(pool_state.read().transactions.is_empty());
+
+		Serp::offchain_worker(5);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, 0);
+		match tx.call {
+			Call::Serp(crate::Call::submit_prices(mut prices)) => {
+				prices.sort_by_key(|(currency_id, _)| *currency_id);
+				assert_eq!(
+					prices,
+					vec![
+						(DNAR, FixedU128::saturating_from_rational(425, 100)),
+						(JUSD, FixedU128::saturating_from_rational(101, 100)),
+					]
+				);
+			}
+			_ => panic!("unexpected call"),
+		}
+	});
+}
+
+#[test]
+fn offchain_worker_submits_unsigned_prices() {
+	const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	let public =
+		SyncCryptoStore::sr25519_generate_new(&keystore, crate::KEY_TYPE, Some(&format!("{}/serp", PHRASE))).unwrap();
+	offchain_state.write().expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:8000/prices".into(),
+		response: Some(br#"{"DNAR": 4.25, "JUSD": 1.01, "SETT": "n/a"}"#.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+	ext.execute_with(|| {
+		System::set_block_number(5);
+		let operator: AccountId = MultiSigner::from(public).into_account();
+		Operators::<Runtime>::put(vec![operator.clone()]);
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), DNAR, Some(b"DNAR".to_vec())));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), SETT, Some(b"SETT".to_vec())));
+
+		// prices are only fetched every `OracleInterval` blocks
+		Serp::offchain_worker(4);
+		assert!(pool_state.read().transactions.is_empty());
+
+		Serp::offchain_worker(5);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		let (payload, signature) = match tx.call {
+			Call::Serp(crate::Call::submit_prices_unsigned(payload, signature)) => (payload, signature),
+			_ => panic!("unexpected call"),
+		};
+		assert_eq!(payload.public, MultiSigner::from(public));
+		assert_eq!(payload.block_number, 5);
+		assert_eq!(payload.nonce, 0);
+		let mut prices = payload.prices.clone();
+		prices.sort_by_key(|(currency_id, _)| *currency_id);
+		assert_eq!(
+			prices,
+			vec![
+				(DNAR, FixedU128::saturating_from_rational(425, 100)),
+				(JUSD, FixedU128::saturating_from_rational(101, 100)),
+			]
+		);
+
+		let call = crate::Call::submit_prices_unsigned(payload.clone(), signature.clone());
+		assert!(Serp::validate_unsigned(TransactionSource::External, &call).is_ok());
+
+		// a tampered payload no longer matches its signature
+		let mut tampered = payload.clone();
+		tampered.prices.pop();
+		assert_eq!(
+			Serp::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::submit_prices_unsigned(tampered, signature.clone())
+			),
+			InvalidTransaction::BadProof.into()
+		);
+
+		// submissions expire after `UnsignedLongevity` blocks
+		System::set_block_number(11);
+		assert_eq!(
+			Serp::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Stale.into()
+		);
+
+		// a failed submission does not use up the nonce
+		System::set_block_number(6);
+		let mut invalid = payload.clone();
+		invalid.prices.push((SETT, FixedU128::zero()));
+		assert_noop!(
+			Serp::submit_prices_unsigned(Origin::none(), invalid.clone(), signature.clone()),
+			Error::<Runtime>::InvalidOraclePrice
+		);
+		assert_eq!(Serp::oracle_nonce(&operator), 0);
+		// and is never valid to be included again
+		assert_eq!(Serp::validate_price_payload(&invalid), InvalidTransaction::Call.into());
+
+		assert_ok!(Serp::submit_prices_unsigned(Origin::none(), payload, signature));
+		assert_eq!(Serp::oracle_nonce(&operator), 1);
+		assert_eq!(Serp::oracle_price(JUSD), Some((FixedU128::saturating_from_rational(101, 100), 6)));
+
+		// and cannot be replayed
+		assert_eq!(
+			Serp::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Stale.into()
+		);
+		Operators::<Runtime>::kill();
+		assert_eq!(
+			Serp::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::BadSigner.into()
+		);
+	});
+}
+
+#[test]
+fn oracle_rounds_aggregate_to_median() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		OracleQuorum::set(3);
+		Operators::<Runtime>::put(vec![ALICE, BOB, SERPER, SETTPAY]);
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+		let price = |cents: u128| FixedU128::saturating_from_rational(cents, 100);
+
+		// a resubmission replaces the operator's earlier price
+		assert_ok!(Serp::submit_prices(Some(ALICE).into(), vec![(JUSD, price(90))]));
+		assert_ok!(Serp::submit_prices(Some(ALICE).into(), vec![(JUSD, price(100))]));
+		assert_ok!(Serp::submit_prices(Some(BOB).into(), vec![(JUSD, price(102))]));
+		assert_eq!(Serp::round_submissions(JUSD, 0), vec![(ALICE, price(100)), (BOB, price(102))]);
+		assert_eq!(Serp::oracle_price(JUSD), None);
+
+		// outliers are dropped, leaving the round short of its quorum
+		assert_ok!(Serp::submit_prices(Some(SETTPAY).into(), vec![(JUSD, price(150))]));
+		let rejected_event = Event::serp(crate::Event::SubmissionRejected(JUSD, SETTPAY, price(150)));
+		assert!(System::events().iter().any(|record| record.event == rejected_event));
+		assert_eq!(Serp::round_submissions(JUSD, 0).len(), 2);
+		assert_eq!(Serp::oracle_price(JUSD), None);
+
+		assert_ok!(Serp::submit_prices(Some(SERPER).into(), vec![(JUSD, price(99))]));
+		assert_eq!(Serp::oracle_price(JUSD), Some((price(100), 1)));
+		assert_eq!(Serp::oracle_round(JUSD), 1);
+		assert!(Serp::round_submissions(JUSD, 0).is_empty());
+		let aggregated_event = Event::serp(crate::Event::AggregatedPrice(JUSD, 0, price(100), 3));
+		assert!(System::events().iter().any(|record| record.event == aggregated_event));
+
+		// a round short of its quorum for `RoundTimeout` blocks is discarded
+		assert_ok!(Serp::submit_prices(Some(ALICE).into(), vec![(JUSD, price(100))]));
+		System::set_block_number(4);
+		assert_ok!(Serp::submit_prices(Some(BOB).into(), vec![(JUSD, price(101))]));
+		let timed_out_event = Event::serp(crate::Event::RoundTimedOut(JUSD, 1));
+		assert!(System::events().iter().any(|record| record.event == timed_out_event));
+		assert!(Serp::round_submissions(JUSD, 1).is_empty());
+		assert_eq!(Serp::oracle_round(JUSD), 2);
+		assert_eq!(Serp::round_submissions(JUSD, 2), vec![(BOB, price(101))]);
+		assert_eq!(Serp::round_start(JUSD), Some(4));
+	});
+}
+
+#[test]
+fn operator_management_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		OracleQuorum::set(3);
+		assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+		assert_noop!(Serp::add_operator(Some(ALICE).into(), BOB), BadOrigin);
+		assert_ok!(Serp::add_operator(Origin::root(), SERPER));
+		assert_ok!(Serp::add_operator(Origin::root(), ALICE));
+		assert_noop!(
+			Serp::add_operator(Origin::root(), ALICE),
+			Error::<Runtime>::AlreadyOperator
+		);
+		assert_ok!(Serp::add_operator(Origin::root(), BOB));
+		assert_ok!(Serp::add_operator(Origin::root(), SETTPAY));
+		assert_noop!(
+			Serp::add_operator(Origin::root(), TREASURY),
+			Error::<Runtime>::TooManyOperators
+		);
+		assert_eq!(Serp::operators(), vec![ALICE, BOB, SERPER, SETTPAY]);
+
+		// removed operators lose their open submissions
+		let price = FixedU128::one();
+		assert_ok!(Serp::submit_prices(Some(BOB).into(), vec![(JUSD, price)]));
+		assert_ok!(Serp::submit_prices(Some(SERPER).into(), vec![(JUSD, price)]));
+		assert_ok!(Serp::remove_operator(Origin::root(), BOB));
+		assert_noop!(
+			Serp::remove_operator(Origin::root(), BOB),
+			Error::<Runtime>::NotOperator
+		);
+		assert_eq!(Serp::round_submissions(JUSD, 0), vec![(SERPER, price)]);
+		assert_noop!(
+			Serp::submit_prices(Some(BOB).into(), vec![(JUSD, price)]),
+			Error::<Runtime>::NotOperator
+		);
+
+		assert_ok!(Serp::swap_operator(Origin::root(), SERPER, TREASURY));
+		assert_noop!(
+			Serp::swap_operator(Origin::root(), SERPER, BOB),
+			Error::<Runtime>::NotOperator
+		);
+		assert_noop!(
+			Serp::swap_operator(Origin::root(), ALICE, TREASURY),
+			Error::<Runtime>::AlreadyOperator
+		);
+		assert_eq!(Serp::operators(), vec![ALICE, SETTPAY, TREASURY]);
+		assert!(Serp::round_submissions(JUSD, 0).is_empty());
+
+		// membership pallets drive the set through `ChangeMembers`
+		<Serp as ChangeMembers<AccountId>>::change_members_sorted(&[BOB], &[TREASURY], &[ALICE, BOB, SETTPAY]);
+		assert_eq!(Serp::operators(), vec![ALICE, BOB, SETTPAY]);
+
+		// members beyond `MaxOperators` are reported rather than silently dropped
+		<Serp as ChangeMembers<AccountId>>::change_members_sorted(
+			&[SERPER, TREASURY],
+			&[],
+			&[ALICE, BOB, SERPER, SETTPAY, TREASURY],
+		);
+		assert_eq!(Serp::operators(), vec![ALICE, BOB, SERPER, SETTPAY]);
+		let truncated_event = Event::serp(crate::Event::OperatorsTruncated(vec![TREASURY]));
+		assert!(System::events().iter().any(|record| record.event == truncated_event));
+	});
+}
+
+#[test]
+fn price_jumps_skip_rounds_and_pause_serp() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::set_max_price_jump(Origin::root(), JUSD, Some(Permill::from_percent(10))));
+			assert_ok!(Serp::on_serp_block(1, JUSD, 1_000, DNAR, 4_000));
+			assert_eq!(Serp::last_serp_price(JUSD), Some(FixedU128::one()));
+
+			// rounds run at the price source's price
+			let jump = FixedU128::saturating_from_rational(12, 10);
+			let step = FixedU128::saturating_from_rational(105, 100);
+			MockPriceSource::set_price(JUSD, Some(jump));
+			assert_ok!(Serp::on_serp_block(2, JUSD, 1_200, DNAR, 4_000));
+			let rejected_event = Event::serp(crate::Event::PriceJumpRejected(JUSD, FixedU128::one(), jump));
+			assert!(System::events().iter().any(|record| record.event == rejected_event));
+			assert_eq!(Serp::price_jump_rejections(JUSD), 1);
+			assert_eq!(Serp::last_serp_price(JUSD), Some(FixedU128::one()));
+
+			// an accepted round resets the count
+			MockPriceSource::set_price(JUSD, Some(step));
+			assert_ok!(Serp::on_serp_block(3, JUSD, 1_050, DNAR, 4_000));
+			assert_eq!(Serp::price_jump_rejections(JUSD), 0);
+
+			MockPriceSource::set_price(JUSD, Some(jump));
+			assert_ok!(Serp::on_serp_block(4, JUSD, 1_200, DNAR, 4_000));
+			assert!(!Serp::serp_paused(JUSD));
+			assert_ok!(Serp::on_serp_block(5, JUSD, 1_200, DNAR, 4_000));
+			assert!(Serp::serp_paused(JUSD));
+			let paused_event = Event::serp(crate::Event::SerpPaused(JUSD));
+			assert!(System::events().iter().any(|record| record.event == paused_event));
+
+			// nothing goes through while paused
+			MockPriceSource::set_price(JUSD, Some(step));
+			assert_ok!(Serp::on_serp_block(6, JUSD, 1_050, DNAR, 4_000));
+			assert_eq!(Serp::last_serp_price(JUSD), Some(step));
+			assert_noop!(Serp::serp_elast(JUSD, 1_050, DNAR, 4_000), Error::<Runtime>::SerpPaused);
+			assert_noop!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000), Error::<Runtime>::SerpPaused);
+			assert_noop!(Serp::contract_supply(DNAR, JUSD, 10 * 1_000, 4_000), Error::<Runtime>::SerpPaused);
+
+			assert_noop!(Serp::unpause_serp(Origin::root(), SETT), Error::<Runtime>::SerpNotPaused);
+			assert_ok!(Serp::unpause_serp(Origin::root(), JUSD));
+			MockPriceSource::set_price(JUSD, Some(jump));
+			assert_ok!(Serp::on_serp_block(7, JUSD, 1_200, DNAR, 3_333));
+			assert_eq!(Serp::last_serp_price(JUSD), Some(jump));
+		});
+}
+
+#[test]
+fn price_fallback_chain_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(DNAR, None);
+			MockFallbackPriceSource::set_price(DNAR, Some(FixedU128::saturating_from_integer(4)));
+			assert_ok!(Serp::on_serp_block(1, JUSD, 1_000, DNAR, 4_000));
+			assert_eq!(Serp::price_source_of(DNAR), Some(PriceSourceKind::Provider(1)));
+			assert_eq!(Serp::price_source_of(JUSD), Some(PriceSourceKind::Provider(0)));
+			assert_eq!(Serp::last_good_price(DNAR), Some((FixedU128::saturating_from_integer(4), 1)));
+			let sourced_event = Event::serp(crate::Event::PriceSourced(
+				DNAR,
+				FixedU128::saturating_from_integer(4),
+				PriceSourceKind::Provider(1),
+			));
+			assert!(System::events().iter().any(|record| record.event == sourced_event));
+
+			// each price of a round is recorded once, quote checks recording none
+			assert_ok!(Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000));
+			for currency_id in [DNAR, JUSD].iter() {
+				let sourced = System::events()
+					.iter()
+					.filter(|record| match record.event {
+						Event::serp(crate::Event::PriceSourced(sourced_id, ..)) => sourced_id == *currency_id,
+						_ => false,
+					})
+					.count();
+				assert_eq!(sourced, 1);
+			}
+
+			// with no source left the last good price carries the SERP for a while
+			MockFallbackPriceSource::set_price(DNAR, None);
+			System::set_block_number(6);
+			assert_eq!(Serp::price(DNAR), Some((FixedU128::saturating_from_integer(4), PriceSourceKind::LastGood)));
+			assert_ok!(Serp::on_serp_block(6, JUSD, 1_000, DNAR, 4_000));
+			assert_eq!(Serp::price_source_of(DNAR), Some(PriceSourceKind::LastGood));
+			assert_eq!(Serp::last_good_price(DNAR), Some((FixedU128::saturating_from_integer(4), 1)));
+
+			System::set_block_number(7);
+			assert_eq!(Serp::price(DNAR), None);
+			assert_noop!(
+				Serp::on_serp_block(7, JUSD, 1_000, DNAR, 4_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+			assert_noop!(
+				Serp::expand_supply(DNAR, JUSD, 10 * 1_000, 4_000),
+				Error::<Runtime>::PriceUnavailable
+			);
+		});
+}
+
+#[test]
+fn commit_reveal_rounds_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(10);
+			Operators::<Runtime>::put(vec![ALICE, BOB, SERPER, SETTPAY]);
+			assert_ok!(Serp::set_oracle_symbol(Origin::root(), JUSD, Some(b"JUSD".to_vec())));
+			assert_noop!(
+				Serp::set_commit_reveal(Origin::root(), Some((3, 0))),
+				Error::<Runtime>::InvalidCommitRevealPeriods
+			);
+			assert_ok!(Serp::set_commit_reveal(Origin::root(), Some((3, 2))));
+
+			let price = FixedU128::saturating_from_rational(101, 100);
+			let salt = [7u8; 32];
+			let hash = BlakeTwo256::hash_of(&(ALICE, JUSD, 0u32, price, salt));
+			assert_noop!(
+				Serp::submit_prices(Some(ALICE).into(), vec![(JUSD, price)]),
+				Error::<Runtime>::CommitRevealActive
+			);
+			assert_noop!(
+				Serp::commit_price(Some(ALICE).into(), JUSD, hash),
+				Error::<Runtime>::OracleNotBonded
+			);
+
+			for who in [ALICE, BOB, SERPER, SETTPAY].iter() {
+				assert_ok!(Serp::bond_oracle(Some(who.clone()).into()));
+				assert_eq!(Serp::reserved_balance(DNAR, who), 10);
+				let own_hash = BlakeTwo256::hash_of(&(who.clone(), JUSD, 0u32, price, salt));
+				// SETTPAY copies ALICE's commitment instead of making its own
+				let hash = if *who == SETTPAY { hash } else { own_hash };
+				assert_ok!(Serp::commit_price(Some(who.clone()).into(), JUSD, hash));
+			}
+			assert_eq!(Serp::cycle_commitments(0).len(), 4);
+			assert_noop!(Serp::bond_oracle(Some(ALICE).into()), Error::<Runtime>::AlreadyBonded);
+			assert_noop!(
+				Serp::commit_price(Some(ALICE).into(), JUSD, hash),
+				Error::<Runtime>::AlreadyCommitted
+			);
+			assert_noop!(
+				Serp::reveal_price(Some(ALICE).into(), JUSD, price, salt),
+				Error::<Runtime>::NotRevealPhase
+			);
+			assert_noop!(Serp::unbond_oracle(Some(ALICE).into()), Error::<Runtime>::CommitmentsOutstanding);
+
+			System::set_block_number(13);
+			assert_noop!(
+				Serp::commit_price(Some(ALICE).into(), JUSD, hash),
+				Error::<Runtime>::NotCommitPhase
+			);
+			assert_ok!(Serp::reveal_price(Some(ALICE).into(), JUSD, price, salt));
+			assert_eq!(Serp::oracle_price(JUSD), Some((price, 13)));
+			let revealed_event = Event::serp(crate::Event::PriceRevealed(JUSD, ALICE, price));
+			assert!(System::events().iter().any(|record| record.event == revealed_event));
+
+			// a reveal not matching its commitment is slashed
+			assert_ok!(Serp::reveal_price(Some(BOB).into(), JUSD, price, [8u8; 32]));
+			assert_eq!(Serp::reserved_balance(DNAR, &BOB), 6);
+			assert_eq!(Serp::oracle_bond(&BOB), 6);
+			let slashed_event = Event::serp(crate::Event::OracleSlashed(JUSD, BOB, 4));
+			assert!(System::events().iter().any(|record| record.event == slashed_event));
+			assert_noop!(
+				Serp::reveal_price(Some(BOB).into(), JUSD, price, salt),
+				Error::<Runtime>::NoCommitment
+			);
+
+			// replaying ALICE's reveal against her copied commitment fails
+			assert_ok!(Serp::reveal_price(Some(SETTPAY).into(), JUSD, price, salt));
+			assert_eq!(Serp::oracle_bond(&SETTPAY), 6);
+			let copied_event = Event::serp(crate::Event::OracleSlashed(JUSD, SETTPAY, 4));
+			assert!(System::events().iter().any(|record| record.event == copied_event));
+
+			// as is a commitment left unrevealed when its cycle ends
+			System::set_block_number(15);
+			Serp::on_initialize(15);
+			assert_eq!(Serp::commitment(JUSD, &SERPER), None);
+			assert_eq!(Serp::reserved_balance(DNAR, &SERPER), 6);
+			assert_eq!(Serp::free_balance(DNAR, &SERPER), 90);
+			assert!(Serp::cycle_commitments(0).is_empty());
+
+			// a partly slashed bond must be topped up before committing again
+			let hash = BlakeTwo256::hash_of(&(BOB, JUSD, 1u32, price, salt));
+			assert_noop!(
+				Serp::commit_price(Some(BOB).into(), JUSD, hash),
+				Error::<Runtime>::OracleNotBonded
+			);
+			assert_ok!(Serp::bond_oracle(Some(BOB).into()));
+			assert_eq!(Serp::oracle_bond(&BOB), 10);
+			assert_eq!(Serp::reserved_balance(DNAR, &BOB), 10);
+			let bonded_event = Event::serp(crate::Event::OracleBonded(BOB, 4));
+			assert!(System::events().iter().any(|record| record.event == bonded_event));
+			assert_ok!(Serp::commit_price(Some(BOB).into(), JUSD, hash));
+			assert_noop!(Serp::unbond_oracle(Some(BOB).into()), Error::<Runtime>::CommitmentsOutstanding);
+			assert_noop!(
+				Serp::set_commit_reveal(Origin::root(), Some((0, 2))),
+				Error::<Runtime>::InvalidCommitRevealPeriods
+			);
+			assert!(Serp::commitment(JUSD, &BOB).is_some());
+
+			assert_ok!(Serp::unbond_oracle(Some(ALICE).into()));
+			assert_eq!(Serp::reserved_balance(DNAR, &ALICE), 0);
+			assert_noop!(Serp::unbond_oracle(Some(ALICE).into()), Error::<Runtime>::OracleNotBonded);
+
+			// disabling commit-reveal drops open commitments unslashed and
+			// restores direct submissions
+			assert_ok!(Serp::set_commit_reveal(Origin::root(), None));
+			assert_eq!(Serp::commitment(JUSD, &BOB), None);
+			assert!(Serp::cycle_commitments(1).is_empty());
+			assert_eq!(Serp::oracle_bond(&BOB), 10);
+			assert_ok!(Serp::unbond_oracle(Some(BOB).into()));
+			assert_ok!(Serp::submit_prices(Some(ALICE).into(), vec![(JUSD, price)]));
+		});
+}
+
+#[test]
+fn liquidity_pools_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Serp::add_liquidity(Some(ALICE).into(), DNAR, 10, 10),
+				Error::<Runtime>::InvalidPoolCurrency
+			);
+			assert_ok!(Serp::add_liquidity(Some(ALICE).into(), JUSD, 10, 40 * 1_000));
+			assert_eq!(Serp::liquidity_shares(JUSD, &ALICE), 10);
+
+			// later providers add at the pool's rate
+			assert_noop!(
+				Serp::add_liquidity(Some(BOB).into(), JUSD, 5, 19 * 1_000),
+				Error::<Runtime>::PoolSlippageExceeded
+			);
+			assert_ok!(Serp::add_liquidity(Some(BOB).into(), JUSD, 5, 25 * 1_000));
+			let added_event = Event::serp(crate::Event::LiquidityAdded(JUSD, BOB, 5, 20 * 1_000, 5));
+			assert!(System::events().iter().any(|record| record.event == added_event));
+			assert_eq!(
+				Serp::liquidity_pool(JUSD),
+				LiquidityPool { native: 15, stable: 60 * 1_000, shares: 15 }
+			);
+			assert_eq!(PoolSpotPrice::<Runtime, MockPriceSource>::get_price(JUSD), Some(FixedU128::one()));
+			assert_eq!(PoolSpotPrice::<Runtime, MockPriceSource>::get_price(DNAR), Some(FixedU128::saturating_from_integer(4)));
+
+			System::set_block_number(3);
+			assert_noop!(
+				Serp::pool_swap(Some(BOB).into(), JUSD, 1_000, SETT, 0),
+				Error::<Runtime>::InvalidPoolCurrency
+			);
+			assert_noop!(
+				Serp::pool_swap(Some(BOB).into(), JUSD, 20 * 1_000, DNAR, 4),
+				Error::<Runtime>::PoolSlippageExceeded
+			);
+			assert_ok!(Serp::pool_swap(Some(BOB).into(), JUSD, 20 * 1_000, DNAR, 3));
+			let swapped_event = Event::serp(crate::Event::PoolSwapped(BOB, JUSD, 20 * 1_000, DNAR, 3));
+			assert!(System::events().iter().any(|record| record.event == swapped_event));
+			assert_eq!(PoolSpotPrice::<Runtime, MockPriceSource>::get_price(JUSD), Some(FixedU128::saturating_from_rational(60, 100)));
+
+			// the TWAP needs a full window first
+			assert_eq!(PoolTwapPrice::<Runtime, MockPriceSource>::get_price(JUSD), None);
+			System::set_block_number(5);
+			assert_eq!(PoolTwapPrice::<Runtime, MockPriceSource>::get_price(JUSD), Some(FixedU128::saturating_from_rational(80, 100)));
+
+			// and keeps the last closed window's average until the next one is full
+			System::set_block_number(6);
+			assert_ok!(Serp::remove_liquidity(Some(BOB).into(), JUSD, 5));
+			let removed_event = Event::serp(crate::Event::LiquidityRemoved(JUSD, BOB, 4, 26_666, 5));
+			assert!(System::events().iter().any(|record| record.event == removed_event));
+			assert_eq!(PoolTwapPrice::<Runtime, MockPriceSource>::get_price(JUSD), Some(FixedU128::saturating_from_rational(76, 100)));
+			assert_noop!(
+				Serp::remove_liquidity(Some(BOB).into(), JUSD, 1),
+				Error::<Runtime>::InsufficientLiquidityShares
+			);
+
+			assert_eq!(Serp::free_balance(DNAR, &BOB), 102);
+			assert_eq!(Serp::free_balance(JUSD, &BOB), 86_666);
+			assert_eq!(Serp::free_balance(DNAR, &Serp::pool_account(JUSD)), 8);
+			assert_eq!(Serp::free_balance(JUSD, &Serp::pool_account(JUSD)), 53_334);
+		});
+}
+
+#[test]
+fn pool_prices_drive_serp_rounds_without_a_feed() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Serp::add_liquidity(Some(ALICE).into(), JUSD, 20, 100 * 1_000));
+			MockPriceSource::set_price(JUSD, None);
+			assert_eq!(Serp::price(JUSD), None);
+
+			// a JUSD at 0.2 DNAR, DNAR being at 4, is at 0.8 of its peg
+			System::set_block_number(5);
+			let price = FixedU128::saturating_from_rational(80, 100);
+			assert_eq!(Serp::price(JUSD), Some((price, PriceSourceKind::Provider(2))));
+			assert_eq!(Serp::reference_price(JUSD, 800), Some(price));
+
+			let contraction = Serp::circulating_supply(JUSD) / 5;
+			assert_eq!(
+				Serp::supply_adjustment(JUSD, price),
+				Some((SupplyAdjustment::Contract(contraction), FixedU128::zero()))
+			);
+			assert_eq!(Serp::supply_change(JUSD, 800), contraction);
+		});
+}
+
+#[test]
+fn peg_stability_module_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let params = PsmParams {
+				mint_fee: Permill::from_percent(1),
+				redeem_fee: Permill::from_percent(2),
+				debt_ceiling: 50 * 1_000,
+			};
+			assert_noop!(Serp::set_psm_params(Some(ALICE).into(), JUSD, SETT, params), BadOrigin);
+			assert_noop!(
+				Serp::set_psm_params(Origin::root(), JUSD, DNAR, params),
+				Error::<Runtime>::InvalidPsmPair
+			);
+			assert_noop!(
+				Serp::psm_mint(Some(ALICE).into(), JUSD, SETT, 10 * 10_000),
+				Error::<Runtime>::PsmPairNotFound
+			);
+			assert_ok!(Serp::set_psm_params(Origin::root(), JUSD, SETT, params));
+
+			// 10 SETT back 10 JUSD, less the 1% mint fee
+			assert_ok!(Serp::psm_mint(Some(ALICE).into(), JUSD, SETT, 10 * 10_000));
+			let minted_event = Event::serp(crate::Event::PsmMinted(JUSD, SETT, ALICE, 10 * 10_000, 9_900, 100));
+			assert!(System::events().iter().any(|record| record.event == minted_event));
+			assert_eq!(Serp::psm_debt(JUSD, SETT), 9_900);
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 109_900);
+			assert_eq!(Serp::free_balance(SETT, &Serp::psm_account()), 10 * 10_000);
+			assert_noop!(
+				Serp::psm_mint(Some(ALICE).into(), JUSD, SETT, 41 * 10_000),
+				Error::<Runtime>::PsmDebtCeilingExceeded
+			);
+
+			// 5 JUSD redeem 5 SETT, less the 2% redeem fee
+			assert_ok!(Serp::psm_redeem(Some(ALICE).into(), JUSD, SETT, 5 * 1_000));
+			let redeemed_event = Event::serp(crate::Event::PsmRedeemed(JUSD, SETT, ALICE, 5 * 1_000, 49_000, 1_000));
+			assert!(System::events().iter().any(|record| record.event == redeemed_event));
+			assert_eq!(Serp::psm_debt(JUSD, SETT), 4_900);
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 104_900);
+			assert_eq!(Serp::free_balance(SETT, &ALICE), 949_000);
+			assert_eq!(Serp::free_balance(SETT, &Serp::psm_account()), 51_000);
+			assert_noop!(
+				Serp::psm_redeem(Some(ALICE).into(), JUSD, SETT, 5 * 1_000),
+				Error::<Runtime>::InsufficientPsmCollateral
+			);
+
+			// amounts that cannot be converted at the peg are refused
+			assert_ok!(Serp::set_psm_params(Origin::root(), SETT, JUSD, params));
+			assert_noop!(
+				Serp::psm_mint(Some(ALICE).into(), SETT, JUSD, Balance::max_value()),
+				Error::<Runtime>::PegConversionOverflow
+			);
+		});
+}
+
+#[test]
+fn mint_and_redeem_at_peg_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockPriceSource::set_price(JUSD, Some(FixedU128::saturating_from_rational(105, 100)));
+			assert_noop!(
+				Serp::mint_at_peg(Some(ALICE).into(), JUSD, 10),
+				Error::<Runtime>::PegWindowClosed
+			);
+			assert_noop!(
+				Serp::set_peg_window_limit(Origin::root(), DNAR, Some(10)),
+				Error::<Runtime>::InvalidPegWindowCurrency
+			);
+			assert_ok!(Serp::set_peg_window_limit(Origin::root(), JUSD, Some(50 * 1_000)));
+
+			// 10 DNAR at 4 each mint 40 JUSD
+			assert_noop!(
+				Serp::redeem_at_peg(Some(ALICE).into(), JUSD, 1_000),
+				Error::<Runtime>::NotBelowPeg
+			);
+			assert_ok!(Serp::mint_at_peg(Some(ALICE).into(), JUSD, 10));
+			let minted_event = Event::serp(crate::Event::MintedAtPeg(JUSD, ALICE, 10, 40 * 1_000));
+			assert!(System::events().iter().any(|record| record.event == minted_event));
+			assert_eq!(Serp::peg_window_used(JUSD), 40 * 1_000);
+			assert_noop!(
+				Serp::mint_at_peg(Some(ALICE).into(), JUSD, 3),
+				Error::<Runtime>::PegWindowLimitExceeded
+			);
+
+			// a paused SERP shuts the window and skips rounds without reopening it
+			MockPriceSource::set_price(JUSD, Some(FixedU128::one()));
+			SerpPaused::<Runtime>::insert(JUSD, true);
+			assert_noop!(
+				Serp::mint_at_peg(Some(ALICE).into(), JUSD, 1),
+				Error::<Runtime>::SerpPaused
+			);
+			assert_ok!(Serp::on_serp_block(2, JUSD, 1_000, DNAR, 4_000));
+			assert_eq!(Serp::peg_window_used(JUSD), 40 * 1_000);
+			assert_ok!(Serp::unpause_serp(Origin::root(), JUSD));
+
+			// the next accepted SERP round opens a fresh window
+			assert_ok!(Serp::on_serp_block(3, JUSD, 1_000, DNAR, 4_000));
+			assert_eq!(Serp::peg_window_used(JUSD), 0);
+
+			MockPriceSource::set_price(JUSD, Some(FixedU128::saturating_from_rational(95, 100)));
+			assert_noop!(
+				Serp::mint_at_peg(Some(ALICE).into(), JUSD, 3),
+				Error::<Runtime>::NotAbovePeg
+			);
+			assert_ok!(Serp::redeem_at_peg(Some(ALICE).into(), JUSD, 8 * 1_000));
+			let redeemed_event = Event::serp(crate::Event::RedeemedAtPeg(JUSD, ALICE, 8 * 1_000, 2));
+			assert!(System::events().iter().any(|record| record.event == redeemed_event));
+			assert_eq!(Serp::free_balance(DNAR, &ALICE), 92);
+			assert_eq!(Serp::free_balance(JUSD, &ALICE), 132 * 1_000);
+
+			// an unhealthy reserve shuts the window
+			assert_ok!(Serp::set_min_collateral_ratio(
+				Origin::root(),
+				JUSD,
+				Some(FixedU128::saturating_from_integer(1))
+			));
+			assert_noop!(
+				Serp::redeem_at_peg(Some(ALICE).into(), JUSD, 1_000),
+				Error::<Runtime>::ReserveRatioUnhealthy
+			);
+		});
+}